@@ -0,0 +1,52 @@
+//! 素数计数函数 π(n) 与第 n 个素数
+use crate::sieve::Primes;
+
+/// 不超过 n 的素数个数 π(n)
+///
+/// 使用 Lucy_Hedgehog 算法, 只在 n/i 的 O(sqrt(n)) 个取值上做筛, 复杂度 O(n^(3/4))
+pub fn prime_count(n: u64) -> u64 {
+    if n < 2 {
+        return 0;
+    }
+    let r = n.isqrt();
+    // small[v] = S(v), large[i] = S(n / i)
+    let mut small: Vec<u64> = (0..=r).map(|v| v.saturating_sub(1)).collect();
+    let mut large: Vec<u64> = (0..=r).map(|i| n.checked_div(i).map_or(0, |v| v - 1)).collect();
+
+    for p in 2..=r {
+        if small[p as usize] == small[p as usize - 1] {
+            continue;                           // p 不是素数
+        }
+        let sp = small[p as usize - 1];         // 小于 p 的素数个数
+        let p2 = p * p;
+
+        // 先更新 large: v = n / i >= p^2
+        let i_max = r.min(n / p2);
+        for i in 1..=i_max {
+            let d = i * p;
+            let s = if d <= r { large[d as usize] } else { small[(n / d) as usize] };
+            large[i as usize] -= s - sp;
+        }
+        // 再更新 small: v >= p^2, 从大到小
+        for v in (p2..=r).rev() {
+            small[v as usize] -= small[(v / p) as usize] - sp;
+        }
+    }
+    large[1]
+}
+
+/// 第 n 个素数 (从 1 开始计数, nth_prime(1) = 2), n 为 0 时返回 None
+pub fn nth_prime(n: u64) -> Option<u64> {
+    if n == 0 {
+        return None;
+    }
+    // 先由 π(x) 粗略定位到 x 附近, 再从该处逐个数
+    let estimate = if n < 6 {
+        0
+    } else {
+        let nf = n as f64;
+        (nf * (nf.ln() + nf.ln().ln() - 1.0)) as u64    // 对 n >= 6 不超过 p_n
+    };
+    let before = prime_count(estimate.saturating_sub(1));
+    Primes::starting_at(estimate).nth((n - before - 1) as usize)
+}
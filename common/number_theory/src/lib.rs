@@ -0,0 +1,12 @@
+//! 各题目共用的素数与数论工具
+//!
+//! - `sieve`: 埃氏筛, 分段筛以及无上界的素数迭代器
+//! - `primality`: 对全部 u64 确定性的 Miller-Rabin 素性测试
+//! - `counting`: 素数计数函数 π(n) 与第 n 个素数
+mod counting;
+mod primality;
+mod sieve;
+
+pub use counting::{nth_prime, prime_count};
+pub use primality::is_prime;
+pub use sieve::{primes, primes_in, Primes, Sieve};
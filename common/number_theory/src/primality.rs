@@ -0,0 +1,83 @@
+//! 确定性 Miller-Rabin 素性测试
+//!
+//! # 参考文献:
+//! - https://miller-rabin.appspot.com/
+//!
+//! # 主要特点:
+//! - 按 n 的大小选取已知的最小确定性底数集合, 对全部 u64 无误判
+//! - 不依赖随机数, 结果可复现
+
+const SMALL_PRIMES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+/// (上界, 底数): n 小于上界时使用对应底数即可保证结果正确
+const WITNESSES: [(u64, &[u64]); 6] = [
+    (1_373_653, &[2, 3]),
+    (25_326_001, &[2, 3, 5]),
+    (4_759_123_141, &[2, 7, 61]),
+    (1_122_004_669_633, &[2, 13, 23, 1_662_803]),
+    (3_474_749_660_383, &[2, 3, 5, 7, 11, 13]),
+    (u64::MAX, &[2, 325, 9375, 28178, 450775, 9780504, 1795265022]),
+];
+
+/// 判断 n 是否为素数
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    // 小素数直接试除, 同时过滤掉大部分合数
+    for p in SMALL_PRIMES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    if n < 41 * 41 {
+        return true;
+    }
+
+    let r = (n - 1).trailing_zeros();
+    let d = (n - 1) >> r;
+    let mut idx = 0;
+    while WITNESSES[idx].0 <= n && idx + 1 < WITNESSES.len() {
+        idx += 1;
+    }
+    for &a in WITNESSES[idx].1 {
+        if !miller_test(a % n, d, r, n) {
+            return false;
+        }
+    }
+    true
+}
+
+/// 以 a 为底数的一轮强伪素数测试, n - 1 = d * 2^r
+fn miller_test(a: u64, d: u64, r: u32, n: u64) -> bool {
+    if a == 0 {
+        return true;
+    }
+    let mut x = pow_mod(a, d, n);
+    if x == 1 || x == n - 1 {
+        return true;
+    }
+    for _ in 1..r {
+        x = mul_mod(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+    false
+}
+
+#[inline]
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut res = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = mul_mod(res, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    res
+}
@@ -0,0 +1,169 @@
+//! 埃拉托斯特尼筛法
+//!
+//! - `Sieve`: 固定上界, 只存储奇数的普通筛
+//! - `primes_in`: 任意区间 [lo, hi) 上的分段筛
+//! - `Primes`: 逐段向后扩展, 没有上界的素数迭代器
+use crate::primality::is_prime;
+
+/// 每一段覆盖的整数个数
+const SEGMENT_SIZE: u64 = 1 << 16;
+/// 基础素数的上界, 可以筛到 2^48 附近; 更大的区间逐个使用 Miller-Rabin 判断
+const MAX_BASE_LIMIT: u64 = 1 << 24;
+
+/// 上界固定的素数筛, 只记录奇数
+#[derive(Debug, Clone)]
+pub struct Sieve {
+    limit: u64,
+    odd_composite: Vec<bool>,       // odd_composite[i] 对应 2i + 1
+}
+
+impl Sieve {
+    /// 筛出 [0, limit] 内的全部素数
+    pub fn new(limit: u64) -> Self {
+        let len = limit.div_ceil(2) as usize;      // 不超过 limit 的奇数个数
+        let mut odd_composite = vec![false; len];
+        if let Some(one) = odd_composite.first_mut() {
+            *one = true;                            // 1 不是素数
+        }
+        let mut p = 3;
+        while p * p <= limit {
+            if !odd_composite[(p / 2) as usize] {
+                // 从 p^2 开始, 步长 2p 只标记奇数
+                for multiple in (p * p..=limit).step_by(2 * p as usize) {
+                    odd_composite[(multiple / 2) as usize] = true;
+                }
+            }
+            p += 2;
+        }
+        Self { limit, odd_composite }
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// n 超出上界时 panic
+    pub fn is_prime(&self, n: u64) -> bool {
+        if n > self.limit { panic!("{} is out of sieve limit {}", n, self.limit) }
+        match n {
+            2 => true,
+            _ if n.is_multiple_of(2) => false,
+            _ => !self.odd_composite[(n / 2) as usize],
+        }
+    }
+
+    /// 按升序返回筛中的全部素数
+    pub fn primes(&self) -> impl Iterator<Item = u64> + '_ {
+        let two = (self.limit >= 2).then_some(2);
+        let odds = self.odd_composite.iter()
+            .enumerate()
+            .filter(|(_, &composite)| !composite)
+            .map(|(i, _)| 2 * i as u64 + 1);
+        two.into_iter().chain(odds)
+    }
+}
+
+/// 使用分段筛求出 [lo, hi) 内的全部素数
+///
+/// 每次只筛 SEGMENT_SIZE 个数, 除结果外额外占用的内存与区间宽度无关
+pub fn primes_in(lo: u64, hi: u64) -> Vec<u64> {
+    if lo >= hi {
+        return Vec::new();
+    }
+    let base_limit = (hi - 1).isqrt().min(MAX_BASE_LIMIT);
+    let base: Vec<u64> = Sieve::new(base_limit).primes().collect();
+    let mut res = Vec::new();
+    let mut segment_lo = lo;
+    while segment_lo < hi {
+        let segment_hi = segment_lo.saturating_add(SEGMENT_SIZE).min(hi);
+        res.extend(sieve_segment(segment_lo, segment_hi, &base, base_limit));
+        segment_lo = segment_hi;
+    }
+    res
+}
+
+/// 使用不超过 base_limit 的基础素数 base 筛出 [lo, hi) 内的素数, hi - lo 不超过 SEGMENT_SIZE
+///
+/// base_limit 不足 sqrt(hi - 1) 时退化为逐个判断
+fn sieve_segment(lo: u64, hi: u64, base: &[u64], base_limit: u64) -> Vec<u64> {
+    debug_assert!(hi - lo <= SEGMENT_SIZE);
+    if (hi - 1).isqrt() > base_limit {
+        return (lo..hi).filter(|&n| is_prime(n)).collect();
+    }
+    let mut composite = vec![false; (hi - lo) as usize];
+    for &p in base {
+        if p * p >= hi {
+            break;
+        }
+        let start = (p * p).max(lo.div_ceil(p) * p);
+        for multiple in (start..hi).step_by(p as usize) {
+            composite[(multiple - lo) as usize] = true;
+        }
+    }
+    composite.iter()
+        .enumerate()
+        .filter(|(i, &c)| !c && lo + *i as u64 >= 2)
+        .map(|(i, _)| lo + i as u64)
+        .collect()
+}
+
+/// 从 2 开始的无上界素数迭代器
+pub fn primes() -> Primes {
+    Primes::new()
+}
+
+/// 逐段筛出素数, 基础素数在需要时扩展
+#[derive(Debug, Clone)]
+pub struct Primes {
+    base: Vec<u64>,             // 不超过 base_limit 的全部素数
+    base_limit: u64,
+    next_segment: Option<u64>,  // 下一段的起点, 到达 u64 上界后为 None
+    buffer: Vec<u64>,           // 当前段内尚未返回的素数 (倒序)
+}
+
+impl Primes {
+    pub fn new() -> Self {
+        Self { base: Vec::new(), base_limit: 0, next_segment: Some(0), buffer: Vec::new() }
+    }
+
+    /// 从不小于 start 的第一个素数开始迭代
+    pub fn starting_at(start: u64) -> Self {
+        Self { next_segment: Some(start), ..Self::new() }
+    }
+
+    fn fill_next_segment(&mut self) -> Option<()> {
+        let lo = self.next_segment?;
+        let hi = lo.saturating_add(SEGMENT_SIZE);
+        self.next_segment = (hi < u64::MAX).then_some(hi);
+
+        let need = (hi - 1).isqrt();
+        if need > self.base_limit {
+            // 每次至少翻倍, 避免频繁重建
+            let limit = need.max(self.base_limit * 2).clamp(1 << 10, MAX_BASE_LIMIT);
+            if limit > self.base_limit {
+                self.base_limit = limit;
+                self.base = Sieve::new(limit).primes().collect();
+            }
+        }
+        self.buffer = sieve_segment(lo, hi, &self.base, self.base_limit);
+        self.buffer.reverse();
+        Some(())
+    }
+}
+
+impl Default for Primes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.buffer.is_empty() {
+            self.fill_next_segment()?;
+        }
+        self.buffer.pop()
+    }
+}
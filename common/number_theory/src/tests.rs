@@ -0,0 +1,41 @@
+// src/tests.rs
+
+#[cfg(test)]
+mod tests {
+    use number_theory::*;
+
+    #[test]
+    fn test_primality() {
+        let small: Vec<u64> = (0..100).filter(|&n| is_prime(n)).collect();
+        assert_eq!(small, Sieve::new(100).primes().collect::<Vec<_>>());
+        assert!(is_prime(18446744073709551557));            // 最大的 u64 素数
+        assert!(!is_prime(3215031751));                     // 底数 2, 3, 5, 7 的强伪素数
+        assert!(!is_prime(u64::MAX));
+
+        let sieve = Sieve::new(1_000_000);
+        assert!((0..=1_000_000).all(|n| sieve.is_prime(n) == is_prime(n)));
+    }
+
+    #[test]
+    fn test_sieve_and_counting() {
+        let first: Vec<u64> = primes().take(10).collect();
+        assert_eq!(first, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes().take_while(|&p| p < 1_000_000).count(), 78498);
+        assert_eq!(primes_in(1_000_000_000, 1_000_000_100), vec![1000000007, 1000000009, 1000000021, 1000000033, 1000000087, 1000000093, 1000000097]);
+        // 跨越多个分段
+        let wide: Vec<u64> = Sieve::new(500_000).primes().filter(|&p| p >= 1000).collect();
+        assert_eq!(primes_in(1000, 500_001), wide);
+        assert_eq!(Primes::starting_at(u64::MAX - 100).collect::<Vec<_>>(), vec![18446744073709551521, 18446744073709551533, 18446744073709551557]);
+
+        assert_eq!(prime_count(1), 0);
+        assert_eq!(prime_count(2), 1);
+        assert_eq!(prime_count(100), 25);
+        assert_eq!(prime_count(10_000_000_000), 455052511);
+
+        assert_eq!(nth_prime(0), None);
+        assert_eq!(nth_prime(1), Some(2));
+        assert_eq!(nth_prime(6), Some(13));
+        assert_eq!(nth_prime(10_001), Some(104743));
+        assert_eq!(nth_prime(1_000_000), Some(15485863));
+    }
+}
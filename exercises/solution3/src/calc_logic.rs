@@ -1,13 +1,392 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// 经典生日问题: 在 365 天等概率的情况下, n 个人中至少两人同一天生日的概率
 pub fn new_birthday_probability(n: u32) -> f64 {
-    if n > 365 {
-        return 1.0_f64;
+    BirthdayProblem::uniform(365)
+        .map(|problem| problem.probability(n))
+        .unwrap_or(0.0)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BirthdayError {
+    NoDays,                     // 天数为 0 或权重列表为空
+    InvalidWeight(usize),       // 第 i 个权重为负数或非有限值
+    ZeroTotalWeight,            // 权重总和为 0
+    InvalidShare(u32),          // "至少 k 人同一天" 中 k < 2
+    InvalidBits(u32),           // 空间位数不在 1..=256 内
+    InvalidRisk(f64),           // 风险预算不在 [0, 1) 内
+}
+
+impl Display for BirthdayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BirthdayError::NoDays => write!(f, "no days in distribution"),
+            BirthdayError::InvalidWeight(idx) => write!(f, "invalid weight at index {}", idx),
+            BirthdayError::ZeroTotalWeight => write!(f, "total weight is zero"),
+            BirthdayError::InvalidShare(k) => write!(f, "share count must be at least 2, got {}", k),
+            BirthdayError::InvalidBits(bits) => write!(f, "bits must be in 1..=256, got {}", bits),
+            BirthdayError::InvalidRisk(risk) => write!(f, "risk must be in [0, 1), got {}", risk),
+        }
+    }
+}
+
+impl std::error::Error for BirthdayError { }
+
+/// 生日在一年中各天上的分布
+#[derive(Debug, Clone, PartialEq)]
+pub enum DayDistribution {
+    /// d 天等概率
+    Uniform(u64),
+    /// 每天的概率 (已归一化), 例如根据真实出生率统计得到的数据
+    Weighted(Vec<f64>),
+}
+
+impl DayDistribution {
+    /// 概率不为 0 的天数
+    fn support(&self) -> u64 {
+        match self {
+            DayDistribution::Uniform(days) => *days,
+            DayDistribution::Weighted(p) => p.iter().filter(|&&x| x > 0.0).count() as u64,
+        }
+    }
+}
+
+/// 广义生日问题: n 个人中至少有 k 个人在同一天生日的概率
+///
+/// # 示例:
+/// ```rust
+/// let problem = BirthdayProblem::uniform(365)?.at_least(3)?;
+/// assert_eq!(problem.min_people(0.5), Some(88));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BirthdayProblem {
+    days: DayDistribution,
+    share: u32,
+}
+
+impl BirthdayProblem {
+    /// d 天等概率, 默认询问 "至少两人同一天"
+    pub fn uniform(days: u64) -> Result<Self, BirthdayError> {
+        if days == 0 {
+            return Err(BirthdayError::NoDays);
+        }
+        Ok(Self { days: DayDistribution::Uniform(days), share: 2 })
+    }
+
+    /// 按照给定的权重 (无需归一化) 生成非均匀的分布
+    pub fn weighted(weights: &[f64]) -> Result<Self, BirthdayError> {
+        if weights.is_empty() {
+            return Err(BirthdayError::NoDays);
+        }
+        if let Some(idx) = weights.iter().position(|w| !w.is_finite() || *w < 0.0) {
+            return Err(BirthdayError::InvalidWeight(idx));
+        }
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return Err(BirthdayError::ZeroTotalWeight);
+        }
+        let probs = weights.iter().map(|w| w / total).collect();
+        Ok(Self { days: DayDistribution::Weighted(probs), share: 2 })
     }
 
-    let mut poss = 1.0_f64;                     // 1 - P(没有人在同一天过生日)
+    /// 改为询问 "至少 k 个人同一天"
+    pub fn at_least(mut self, k: u32) -> Result<Self, BirthdayError> {
+        if k < 2 {
+            return Err(BirthdayError::InvalidShare(k));
+        }
+        self.share = k;
+        Ok(self)
+    }
 
-    for i in 0..n {
-        poss *= (365 - i) as f64 / 365.0_f64;       // FIXME: four-digit decimation
+    /// 由鸽巢原理, 超过该人数后必定有 k 个人同一天
+    fn certain_after(&self) -> u64 {
+        self.days.support().saturating_mul(self.share as u64 - 1)
     }
 
-    1.0_f64 - poss
+    /// n 个人中至少有 k 个人同一天生日的概率
+    pub fn probability(&self, n: u32) -> f64 {
+        if n as u64 > self.certain_after() {
+            return 1.0;
+        }
+        match (&self.days, self.share) {
+            (DayDistribution::Uniform(days), 2) => -ln_all_distinct(*days, n).exp_m1(),
+            _ => 1.0 - self.no_share_table(n)[n as usize],
+        }
+    }
+
+    /// 反向查询: 概率达到 target 所需的最少人数
+    ///
+    /// target 不在 [0, 1] 内时返回 None
+    pub fn min_people(&self, target: f64) -> Option<u32> {
+        if !(0.0..=1.0).contains(&target) {
+            return None;
+        }
+        let cap = u32::try_from(self.certain_after().saturating_add(1)).unwrap_or(u32::MAX);
+
+        if let (DayDistribution::Uniform(days), 2) = (&self.days, self.share) {
+            // 逐个累加对数, 不需要一次性分配整张表
+            let mut ln_p = 0.0_f64;
+            for n in 0..cap {
+                if -ln_p.exp_m1() >= target {
+                    return Some(n);
+                }
+                ln_p += (-(n as f64) / *days as f64).ln_1p();
+            }
+            return Some(cap);
+        }
+
+        // 概率随人数单调不减: 先倍增找到上界, 再二分, 只需计算 O(log n) 个人数的概率
+        if self.probability(0) >= target {
+            return Some(0);
+        }
+        let mut hi = 1;
+        while hi < cap && self.probability(hi) < target {
+            hi = hi.saturating_mul(2).min(cap);
+        }
+        let mut lo = hi / 2;                                // probability(lo) < target
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.probability(mid) >= target { hi = mid } else { lo = mid }
+        }
+        Some(hi)
+    }
+
+    /// 蒙特卡洛模拟, 用于交叉验证解析结果
+    ///
+    /// 相同的 seed 总会得到相同的结果
+    pub fn simulate(&self, n: u32, trials: u32, seed: u64) -> f64 {
+        if trials == 0 {
+            return 0.0;
+        }
+        let mut rng = SplitMix64::new(seed);
+        let cumulative: Vec<f64> = match &self.days {
+            DayDistribution::Weighted(p) => p
+                .iter()
+                .scan(0.0, |acc, x| {
+                    *acc += x;
+                    Some(*acc)
+                })
+                .collect(),
+            DayDistribution::Uniform(_) => Vec::new(),
+        };
+
+        let mut counter: HashMap<u64, u32> = HashMap::with_capacity(n as usize);
+        let mut hits = 0;
+        for _ in 0..trials {
+            counter.clear();
+            for _ in 0..n {
+                let day = match &self.days {
+                    DayDistribution::Uniform(days) => rng.next_below(*days),
+                    DayDistribution::Weighted(_) => {
+                        let x = rng.next_f64() * cumulative[cumulative.len() - 1];
+                        cumulative.partition_point(|&c| c <= x).min(cumulative.len() - 1) as u64
+                    }
+                };
+                let cnt = counter.entry(day).or_insert(0);
+                *cnt += 1;
+                if *cnt >= self.share {
+                    hits += 1;
+                    break;
+                }
+            }
+        }
+        hits as f64 / trials as f64
+    }
+
+    /// table[m] = m 个人中没有任何一天出现 k 个人的概率 (m <= max_n)
+    ///
+    /// 单独一天的表为 h[j] = p^j (j < k), 合并两组天数的表为二项卷积:
+    /// (a ⊛ b)[m] = sum_j C(m, j) * a[j] * b[m - j]
+    /// - 非均匀分布逐天合并, 复杂度 O(天数 * max_n * k)
+    /// - d 天等概率时结果为 h 的 d 次二项卷积幂, 用快速幂计算, 复杂度 O(max_n^2 * log d)
+    ///
+    /// 过程中每一项都是概率, 不会溢出
+    fn no_share_table(&self, max_n: u32) -> Vec<f64> {
+        let len = max_n as usize + 1;
+        let share = self.share as usize;
+        let day_table = |p: f64| -> Vec<f64> {
+            (0..len).map(|j| if j < share { p.powi(j as i32) } else { 0.0 }).collect()
+        };
+        let mut table = vec![0.0_f64; len];
+        table[0] = 1.0;
+
+        match &self.days {
+            DayDistribution::Uniform(days) => {
+                let ln_fact = ln_factorials(len);
+                let mut base = day_table(1.0 / *days as f64);
+                let mut exp = *days;
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        table = binomial_convolve(&table, &base, &ln_fact);
+                    }
+                    exp >>= 1;
+                    if exp > 0 {
+                        base = binomial_convolve(&base, &base, &ln_fact);
+                    }
+                }
+            }
+            DayDistribution::Weighted(p) => {
+                for &x in p.iter().filter(|&&x| x > 0.0) {
+                    for m in (1..len).rev() {
+                        let mut acc = table[m];
+                        let mut coef = 1.0_f64;         // C(m, j) * p^j
+                        for j in 1..share.min(m + 1) {
+                            coef *= (m + 1 - j) as f64 / j as f64 * x;
+                            acc += coef * table[m - j];
+                        }
+                        table[m] = acc;
+                    }
+                }
+            }
+        }
+        table
+    }
+}
+
+/// ln_fact[i] = ln(i!), i < len
+fn ln_factorials(len: usize) -> Vec<f64> {
+    let mut ln_fact = Vec::with_capacity(len);
+    let mut acc = 0.0_f64;
+    for i in 0..len {
+        if i > 1 {
+            acc += (i as f64).ln();
+        }
+        ln_fact.push(acc);
+    }
+    ln_fact
+}
+
+/// 二项卷积 (a ⊛ b)[m] = sum_j C(m, j) * a[j] * b[m - j]
+///
+/// 每一项在对数空间中相乘, m 较大时 C(m, j) 本身溢出也不影响结果
+fn binomial_convolve(a: &[f64], b: &[f64], ln_fact: &[f64]) -> Vec<f64> {
+    let ln_a: Vec<f64> = a.iter().map(|x| x.ln()).collect();
+    let ln_b: Vec<f64> = b.iter().map(|x| x.ln()).collect();
+    (0..a.len())
+        .map(|m| {
+            (0..=m)
+                .filter(|&j| a[j] > 0.0 && b[m - j] > 0.0)
+                .map(|j| (ln_fact[m] - ln_fact[j] - ln_fact[m - j] + ln_a[j] + ln_b[m - j]).exp())
+                .sum()
+        })
+        .collect()
+}
+
+/// ln P(n 个人生日两两不同), 在对数空间中累加以保证 n 较大时的精度
+fn ln_all_distinct(days: u64, n: u32) -> f64 {
+    (0..n as u64).map(|i| (-(i as f64) / days as f64).ln_1p()).sum()
+}
+
+/*
+    以下为基于生日界的哈希/随机 ID 碰撞概率计算, 空间大小为 2^bits
+ */
+
+/// 直接逐项累加 ln(1 - i/N) 的最大项数
+const DIRECT_SUM_LIMIT: u128 = 1 << 20;
+
+/// 在 2^bits 的空间中随机取 n 个值, 至少出现一次碰撞的概率
+///
+/// 全程在对数空间中计算, bits 最大支持 256, 碰撞概率极小 (如 1e-60) 时依然精确
+pub fn collision_probability(n: u128, bits: u32) -> Result<f64, BirthdayError> {
+    Ok(-ln_no_collision(n, space_size(bits)?).exp_m1())
+}
+
+/// 在 2^bits 的空间中随机取 n 个值, 发生碰撞的值对数量的期望, 即 C(n, 2) / 2^bits
+pub fn expected_collisions(n: u128, bits: u32) -> Result<f64, BirthdayError> {
+    let space = space_size(bits)?;
+    let n = n as f64;
+    Ok(n * (n - 1.0) / 2.0 / space)
+}
+
+/// 碰撞概率不超过 risk 时最多可以取多少个值
+pub fn max_items(risk: f64, bits: u32) -> Result<u128, BirthdayError> {
+    let space = space_size(bits)?;
+    if !(0.0..1.0).contains(&risk) {
+        return Err(BirthdayError::InvalidRisk(risk));
+    }
+    let within = |n: u128| -ln_no_collision(n, space).exp_m1() <= risk;
+
+    // 先用 n ≈ sqrt(2N ln(1/(1-p))) 估计, 再在其附近二分
+    let estimate = (2.0 * space * -(-risk).ln_1p()).sqrt();
+    let mut lo: u128 = 1;
+    let mut hi: u128 = (estimate * 2.0 + 2.0).min(u128::MAX as f64) as u128;
+    while within(hi) {
+        if hi == u128::MAX {
+            return Ok(hi);                                  // 超出 u128 的表示范围
+        }
+        hi = hi.saturating_mul(2);
+    }
+    // 不变式: within(lo) && !within(hi)
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if within(mid) { lo = mid } else { hi = mid }
+    }
+    Ok(lo)
+}
+
+fn space_size(bits: u32) -> Result<f64, BirthdayError> {
+    if !(1..=256).contains(&bits) {
+        return Err(BirthdayError::InvalidBits(bits));
+    }
+    Ok(2.0_f64.powi(bits as i32))
+}
+
+/// ln P(n 个值两两不同) = sum_{i < n} ln(1 - i/N)
+///
+/// - n/N 很小时展开为 -sum_k S_k / (k N^k), S_k = sum_{i < n} i^k, 避免大数相减
+/// - n 不大时直接累加
+/// - 其余情况使用 Stirling 公式: -(N - n + 1/2) ln(1 - n/N) - n + 1/(12N) - 1/(12(N - n))
+fn ln_no_collision(n: u128, space: f64) -> f64 {
+    let nf = n as f64;
+    if nf > space {
+        return f64::NEG_INFINITY;
+    }
+    let x = nf / space;
+    if x < 1e-4 {
+        let s1 = nf * (nf - 1.0) / 2.0;
+        let s2 = s1 * (2.0 * nf - 1.0) / 3.0;
+        let s3 = s1 * s1;
+        let s4 = s2 * (3.0 * nf * nf - 3.0 * nf - 1.0) / 5.0;
+        -(s1 / space + s2 / (2.0 * space.powi(2)) + s3 / (3.0 * space.powi(3)) + s4 / (4.0 * space.powi(4)))
+    } else if n <= DIRECT_SUM_LIMIT {
+        (0..n).map(|i| (-(i as f64) / space).ln_1p()).sum()
+    } else {
+        let rest = space - nf;
+        if rest < 1.0 {
+            return f64::NEG_INFINITY;                       // n == N 且 N 很大, e^{-N} 已下溢
+        }
+        -(rest + 0.5) * (-x).ln_1p() - nf + 1.0 / (12.0 * space) - 1.0 / (12.0 * rest)
+    }
+}
+
+/// 简单的可设定种子的伪随机数生成器
+/// 参照 https://prng.di.unimi.it/splitmix64.c
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// [0, 1) 上的均匀分布
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// [0, bound) 上的整数, 使用乘法取高位代替取模
+    pub fn next_below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
 }
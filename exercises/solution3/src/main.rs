@@ -1,6 +1,28 @@
 mod calc_logic;
 
+const USAGE: &str = "用法:
+    solution3                           经典生日问题示例
+    solution3 prob <n> <bits>           n 个值在 2^bits 空间中至少一次碰撞的概率
+    solution3 expect <n> <bits>         n 个值在 2^bits 空间中碰撞对数量的期望
+    solution3 max-items <risk> <bits>   碰撞概率不超过 risk 时最多可取的值数量";
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        birthday_demo();
+        return;
+    }
+
+    match collision_cli(&args) {
+        Ok(output) => println!("{output}"),
+        Err(msg) => {
+            eprintln!("{msg}\n\n{USAGE}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn birthday_demo() {
     // 更改这个值来测试不同的人数
     let n: u32 = 78;
     let probability = calc_logic::new_birthday_probability(n);
@@ -9,4 +31,56 @@ fn main() {
         n,
         probability
     );
+
+    let triple = calc_logic::BirthdayProblem::uniform(365)
+        .and_then(|problem| problem.at_least(3))
+        .unwrap();
+    println!(
+        "在 {} 个人中，有三个人在同一天过生日的概率是 {:.4} (模拟值 {:.4})，超过一半至少需要 {:?} 人",
+        n,
+        triple.probability(n),
+        triple.simulate(n, 10_000, 0),
+        triple.min_people(0.5)
+    );
+
+    // 各月出生人数并不均匀, 按每月的相对出生率给每一天加权
+    let weights: Vec<f64> = MONTHLY_BIRTH_RATE
+        .iter()
+        .flat_map(|&(days, rate)| std::iter::repeat_n(rate, days))
+        .collect();
+    match calc_logic::BirthdayProblem::weighted(&weights) {
+        Ok(seasonal) => println!(
+            "按各月出生率加权时，{} 个人中有两个人在同一天过生日的概率是 {:.4}，超过一半至少需要 {:?} 人",
+            n,
+            seasonal.probability(n),
+            seasonal.min_people(0.5)
+        ),
+        Err(e) => eprintln!("{e}"),
+    }
+}
+
+/// 各月的天数与相对出生率 (夏末秋初略高, 春季略低)
+const MONTHLY_BIRTH_RATE: [(usize, f64); 12] = [
+    (31, 0.96), (28, 0.97), (31, 0.99), (30, 0.96), (31, 0.99), (30, 1.01),
+    (31, 1.05), (31, 1.07), (30, 1.06), (31, 1.00), (30, 0.97), (31, 0.98),
+];
+
+/// 解析命令行参数并执行对应的碰撞概率计算
+fn collision_cli(args: &[String]) -> Result<String, String> {
+    let [cmd, value, bits] = args else {
+        return Err(format!("参数数量错误: {}", args.len()));
+    };
+    let bits: u32 = bits.parse().map_err(|e| format!("无法解析 bits '{bits}': {e}"))?;
+    let parse_n = || value.parse::<u128>().map_err(|e| format!("无法解析 n '{value}': {e}"));
+
+    match cmd.as_str() {
+        "prob" => calc_logic::collision_probability(parse_n()?, bits).map(|p| format!("{p:e}")),
+        "expect" => calc_logic::expected_collisions(parse_n()?, bits).map(|e| format!("{e:e}")),
+        "max-items" => {
+            let risk: f64 = value.parse().map_err(|e| format!("无法解析 risk '{value}': {e}"))?;
+            calc_logic::max_items(risk, bits).map(|n| n.to_string())
+        }
+        _ => return Err(format!("未知命令: {cmd}")),
+    }
+    .map_err(|e| e.to_string())
 }
@@ -3,7 +3,10 @@ mod calc_logic;
 
 #[cfg(test)]
 mod tests {
-    use super::calc_logic::new_birthday_probability;
+    use super::calc_logic::{
+        collision_probability, expected_collisions, max_items, new_birthday_probability,
+        BirthdayError, BirthdayProblem,
+    };
     // 定义测试用例和预期结果
     const TEST_CASES: &[(u32, f64)] = &[
         (23, 0.5073),
@@ -34,5 +37,68 @@ mod tests {
         println!("Total score: {:.2}", total_score);
         assert_eq!(100.00, total_score);
     }
+
+    #[test]
+    fn test_generalised_birthday() {
+        let classic = BirthdayProblem::uniform(365).unwrap();
+        assert_eq!(classic.min_people(0.5), Some(23));
+        assert_eq!(classic.min_people(1.5), None);
+        assert!((classic.probability(23) - 0.5073).abs() < 0.0001);
+
+        // 至少三人同一天需要 88 人才能超过一半
+        let triple = classic.clone().at_least(3).unwrap();
+        assert_eq!(triple.min_people(0.5), Some(88));
+
+        // 均匀权重应与解析解一致
+        let weighted = BirthdayProblem::weighted(&[1.0; 365]).unwrap();
+        for n in [10, 23, 50] {
+            assert!((weighted.probability(n) - classic.probability(n)).abs() < 1e-9);
+        }
+
+        // 等概率时的快速幂与逐天合并一致
+        let weighted_triple = BirthdayProblem::weighted(&[1.0; 365]).unwrap().at_least(3).unwrap();
+        for n in [50, 88, 150] {
+            assert!((weighted_triple.probability(n) - triple.probability(n)).abs() < 1e-9);
+        }
+        // 天数很大时也能很快算出, 约为 1 - e^{-C(n, 3) / d^2}
+        let huge = BirthdayProblem::uniform(1_000_000_000).unwrap().at_least(3).unwrap();
+        let expected = -(-(1000.0 * 999.0 * 998.0 / 6.0) / 1e18_f64).exp_m1();
+        assert!((huge.probability(1000) / expected - 1.0).abs() < 1e-3);
+
+        // 蒙特卡洛结果与解析解交叉验证, 相同种子结果一致
+        let simulated = classic.simulate(23, 20_000, 42);
+        assert_eq!(simulated, classic.simulate(23, 20_000, 42));
+        assert!((simulated - classic.probability(23)).abs() < 0.02);
+
+        assert_eq!(BirthdayProblem::uniform(0), Err(BirthdayError::NoDays));
+        assert_eq!(BirthdayProblem::weighted(&[1.0, -1.0]), Err(BirthdayError::InvalidWeight(1)));
+        assert_eq!(classic.at_least(1), Err(BirthdayError::InvalidShare(1)));
+    }
+
+    #[test]
+    fn test_collision_toolkit() {
+        // 空间较小时与生日问题的解析解一致
+        let small = BirthdayProblem::uniform(1 << 16).unwrap();
+        for n in [10, 300, 1000] {
+            let p = collision_probability(n, 16).unwrap();
+            assert!((p - small.probability(n as u32)).abs() < 1e-9);
+        }
+
+        // 2^128 空间中 2^64 个值的碰撞概率约为 1 - e^{-1/2}
+        let p = collision_probability(1 << 64, 128).unwrap();
+        assert!((p - 0.3934693402873666).abs() < 1e-12);
+        // 极小的概率依然保持相对精度
+        let tiny = collision_probability(1_000_000, 256).unwrap();
+        let approx = expected_collisions(1_000_000, 256).unwrap();
+        assert!((tiny / approx - 1.0).abs() < 1e-9);
+
+        // 32 位哈希在 50% 风险下约可容纳 77163 个值
+        assert_eq!(max_items(0.5, 32).unwrap(), 77163);
+        let n = max_items(1e-6, 64).unwrap();
+        assert!(collision_probability(n, 64).unwrap() <= 1e-6);
+        assert!(collision_probability(n + 1, 64).unwrap() > 1e-6);
+
+        assert_eq!(collision_probability(1, 0), Err(BirthdayError::InvalidBits(0)));
+        assert_eq!(max_items(1.0, 64), Err(BirthdayError::InvalidRisk(1.0)));
+    }
 }
-    
\ No newline at end of file
@@ -0,0 +1,87 @@
+//! 判断货币体系是否为 "规范的" (canonical), 即贪心算法总能得到最少硬币数
+//!
+//! # 参考文献:
+//! - Kozen, D., Zaks, S. Optimal bounds for the change-making problem. 1994.
+//! - Pearson, D. A polynomial-time algorithm for the change-making problem. 2005.
+//!
+//! # 主要结论:
+//! - 包含面值 1 且币值升序为 c_1 < ... < c_n 时, 若存在反例,
+//!   则最小反例 x 满足 c_3 + 1 < x < c_{n-1} + c_n
+//! - 币值降序为 c_1 > ... > c_n = 1 时, 最小反例的最优表示在第 1..j-1 项上与 c_{i-1} - 1 的贪心表示相同,
+//!   第 j 项多 1, 其余为 0; 只需检验 O(n^2) 个候选金额, 不必建立与金额同样大的 DP 表
+use std::ops::RangeInclusive;
+use crate::rec_mc::{CoinError, CoinSystem};
+
+/// 贪心与最优解不一致的金额
+#[derive(Debug, PartialEq)]
+pub struct Disagreement {
+    pub amount: u32,
+    pub greedy: Option<u32>,    // 贪心无法凑出时为 None
+    pub optimal: u32,
+}
+
+impl CoinSystem {
+    /// 每次取不超过剩余金额的最大币值, 返回使用的硬币 (降序)
+    pub fn greedy_change(&self, amount: u32) -> Option<Vec<u32>> {
+        let mut res = Vec::new();
+        let mut rest = amount;
+        for &coin in self.denominations().iter().rev() {
+            res.extend(std::iter::repeat_n(coin, (rest / coin) as usize));
+            rest %= coin;
+        }
+        (rest == 0).then_some(res)
+    }
+
+    /// 贪心算法是否对所有金额都给出最优解
+    pub fn is_canonical(&self) -> Result<bool, CoinError> {
+        Ok(self.smallest_counterexample()?.is_none())
+    }
+
+    /// 最小的贪心非最优金额, 规范体系返回 None, 时间复杂度 O(n^3)
+    pub fn smallest_counterexample(&self) -> Result<Option<u32>, CoinError> {
+        let coins = self.denominations();
+        if coins[0] != 1 {
+            return Err(CoinError::MissingUnitCoin);
+        }
+        // 降序排列, 与 Pearson 的记号一致; 候选金额小于 c_{i-1} + c_j, 使用 u64 不会溢出
+        let desc: Vec<u64> = coins.iter().rev().map(|&c| c as u64).collect();
+        let greedy = |amount: u64| -> Vec<u64> {
+            let mut rest = amount;
+            desc.iter()
+                .map(|&c| {
+                    let cnt = rest / c;
+                    rest %= c;
+                    cnt
+                })
+                .collect()
+        };
+
+        let mut smallest: Option<u64> = None;
+        for i in 1..desc.len() {
+            let base = greedy(desc[i - 1] - 1);
+            for j in i..desc.len() {
+                let count = base[..j].iter().sum::<u64>() + base[j] + 1;
+                let amount = base[..j].iter().zip(&desc).map(|(cnt, c)| cnt * c).sum::<u64>() + (base[j] + 1) * desc[j];
+                if greedy(amount).iter().sum::<u64>() > count && smallest.is_none_or(|x| amount < x) {
+                    smallest = Some(amount);
+                }
+            }
+        }
+        smallest.map(|amount| u32::try_from(amount).map_err(|_| CoinError::SearchOverflow)).transpose()
+    }
+
+    /// 区间内所有贪心与最优解不一致的金额, 需要建立大小为 range.end() 的 DP 表
+    pub fn disagreements(&self, range: RangeInclusive<u32>) -> Vec<Disagreement> {
+        if range.is_empty() {
+            return Vec::new();
+        }
+        let dp = self.min_coins_table(*range.end());
+        range
+            .filter_map(|amount| {
+                let optimal = dp[amount as usize]?;
+                let greedy = self.greedy_change(amount).map(|coins| coins.len() as u32);
+                (greedy != Some(optimal)).then_some(Disagreement { amount, greedy, optimal })
+            })
+            .collect()
+    }
+}
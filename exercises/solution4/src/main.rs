@@ -1,7 +1,21 @@
+mod canonical;
 mod rec_mc;
 
 fn main() {
     let amount = 93u32;
     let cashe_num = rec_mc::dp_rec_mc(amount);
     println!("{cashe_num}");
+
+    let system = rec_mc::CoinSystem::new(&rec_mc::DEFAULT_COINS).unwrap();
+    println!("{:?}", system.make_change(amount));
+    println!("ways: {}", system.count_ways(amount));
+    // 每种硬币各 1 枚时的找零方案
+    let stock = vec![1; system.denominations().len()];
+    println!("{:?}", system.make_change_limited(amount, &stock));
+
+    // 贪心是否总是最优
+    println!("canonical: {:?}, counterexample: {:?}", system.is_canonical(), system.smallest_counterexample());
+    for d in system.disagreements(1..=200) {
+        println!("{}: greedy {:?}, optimal {}", d.amount, d.greedy, d.optimal);
+    }
 }
@@ -1,14 +1,162 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+/// 题目给定的币值
+pub const DEFAULT_COINS: [u32; 8] = [1, 2, 5, 10, 20, 30, 50, 100];
+
+/// 使用题目给定的币值凑出 amount 所需的最少硬币数量
 pub fn dp_rec_mc(amount: u32) -> u32 {
-    let coin_price: [usize; 8] = [1, 2, 5, 10, 20, 30, 50, 100];
-    let mut dp = vec![std::usize::MAX; amount as usize + 1]; // Currently optimal number of money
-    dp[0] = 0;
+    CoinSystem::new(&DEFAULT_COINS)
+        .expect("default coins are valid")
+        .min_coins(amount)
+        .expect("default coins contain 1, every amount is reachable")
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CoinError {
+    Empty,                  // 未提供任何币值
+    ZeroDenomination,       // 币值为 0
+    MissingUnitCoin,        // 缺少面值为 1 的硬币
+    SearchOverflow,         // 最小反例超出 u32
+}
 
-    // dp[i] = min(dp[i], dp[i - coin[j]] + 1)
-    for i in 0..=amount as usize {
-        for j in coin_price.iter().rev().filter(|&&x| x <= amount as usize && x <= i) {
-            dp[i as usize] = std::cmp::min(dp[i], dp[i - j] + 1);
+impl Display for CoinError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CoinError::Empty => write!(f, "no denominations given"),
+            CoinError::ZeroDenomination => write!(f, "denomination must be positive"),
+            CoinError::MissingUnitCoin => write!(f, "coin system has no unit coin"),
+            CoinError::SearchOverflow => write!(f, "smallest counterexample exceeds u32"),
         }
     }
+}
+
+impl std::error::Error for CoinError { }
+
+/// 任意币值组成的货币体系
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoinSystem {
+    coins: Vec<u32>,        // 升序且去重
+}
+
+impl CoinSystem {
+    pub fn new(denominations: &[u32]) -> Result<Self, CoinError> {
+        if denominations.is_empty() {
+            return Err(CoinError::Empty);
+        }
+        if denominations.contains(&0) {
+            return Err(CoinError::ZeroDenomination);
+        }
+        let mut coins = denominations.to_vec();
+        coins.sort_unstable();
+        coins.dedup();
+        Ok(Self { coins })
+    }
+
+    /// 升序排列的币值
+    pub fn denominations(&self) -> &[u32] {
+        &self.coins
+    }
+
+    /// 凑出 amount 所需的最少硬币数量, 无法凑出时返回 None
+    pub fn min_coins(&self, amount: u32) -> Option<u32> {
+        self.min_coins_table(amount)[amount as usize]
+    }
 
-    dp[amount as usize] as u32
+    /// 凑出 amount 的一种最少硬币方案 (降序), 无法凑出时返回 None
+    pub fn make_change(&self, amount: u32) -> Option<Vec<u32>> {
+        let dp = self.min_coins_table(amount);
+        dp[amount as usize]?;
+
+        // 沿着 dp[i] = dp[i - coin] + 1 倒推出使用的硬币
+        let mut res = Vec::new();
+        let mut rest = amount as usize;
+        while rest > 0 {
+            let coin = self.coins.iter().rev()
+                .map(|&c| c as usize)
+                .find(|&c| c <= rest && dp[rest - c].is_some_and(|n| n + 1 == dp[rest].unwrap()))?;
+            res.push(coin as u32);
+            rest -= coin;
+        }
+        Some(res)
+    }
+
+    /// 凑出 amount 的不同组合数 (不计顺序), 超出 u128 时饱和
+    pub fn count_ways(&self, amount: u32) -> u128 {
+        let mut ways = vec![0_u128; amount as usize + 1];
+        ways[0] = 1;
+        for &coin in &self.coins {
+            for i in coin as usize..=amount as usize {
+                ways[i] = ways[i].saturating_add(ways[i - coin as usize]);
+            }
+        }
+        ways[amount as usize]
+    }
+
+    /// 每种硬币数量有限 (多重背包) 时的最少硬币方案 (降序)
+    ///
+    /// stock[i] 为 denominations()[i] 的库存, 缺省视为 0
+    /// 对每种币值按余数分组, 使用单调队列维护窗口最小值, 复杂度 O(币值种类 * amount)
+    pub fn make_change_limited(&self, amount: u32, stock: &[u32]) -> Option<Vec<u32>> {
+        let amount = amount as usize;
+        let mut dp: Vec<Option<u32>> = vec![None; amount + 1];
+        dp[0] = Some(0);
+        // take[i][a]: 凑出 a 时第 i 种币值使用的数量
+        let mut take = vec![vec![0_u32; amount + 1]; self.coins.len()];
+
+        for (i, &coin) in self.coins.iter().enumerate() {
+            let limit = stock.get(i).copied().unwrap_or(0) as usize;
+            let coin = coin as usize;
+            let prev = dp.clone();
+
+            for r in 0..coin.min(amount + 1) {
+                // 队列中保存 (j, prev[r + j * coin] - j), 值单调递增
+                let mut window: VecDeque<(usize, i64)> = VecDeque::new();
+                for (j, a) in (r..=amount).step_by(coin).enumerate() {
+                    if let Some(cnt) = prev[a] {
+                        let value = cnt as i64 - j as i64;
+                        while window.back().is_some_and(|&(_, v)| v >= value) {
+                            window.pop_back();
+                        }
+                        window.push_back((j, value));
+                    }
+                    while window.front().is_some_and(|&(k, _)| k + limit < j) {
+                        window.pop_front();
+                    }
+                    match window.front() {
+                        Some(&(k, value)) => {
+                            dp[a] = Some((value + j as i64) as u32);
+                            take[i][a] = (j - k) as u32;
+                        }
+                        None => dp[a] = None,
+                    }
+                }
+            }
+        }
+
+        dp[amount]?;
+        let mut res = Vec::new();
+        let mut rest = amount;
+        for (i, &coin) in self.coins.iter().enumerate().rev() {
+            let cnt = take[i][rest];
+            res.extend(std::iter::repeat_n(coin, cnt as usize));
+            rest -= cnt as usize * coin as usize;
+        }
+        Some(res)
+    }
+
+    /// dp[i] = min(dp[i], dp[i - coin[j]] + 1), None 表示无法凑出
+    pub(crate) fn min_coins_table(&self, amount: u32) -> Vec<Option<u32>> {
+        let mut dp: Vec<Option<u32>> = vec![None; amount as usize + 1];
+        dp[0] = Some(0);
+        for i in 1..=amount as usize {
+            dp[i] = self.coins.iter()
+                .map(|&c| c as usize)
+                .take_while(|&c| c <= i)
+                .filter_map(|c| dp[i - c])
+                .min()
+                .map(|n| n + 1);
+        }
+        dp
+    }
 }
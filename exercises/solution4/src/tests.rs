@@ -1,10 +1,11 @@
 
 // src/tests.rs
+mod canonical;
 mod rec_mc;
 
 #[cfg(test)]
 mod tests {
-    use super::rec_mc::dp_rec_mc;
+    use super::rec_mc::{dp_rec_mc, CoinError, CoinSystem};
     // 定义测试用例和预期结果
     const TEST_CASES: &[(u32, u32)] = &[
         (90, 3),
@@ -27,5 +28,71 @@ mod tests {
         println!("Total score: {:.2}", total_score);
         assert_eq!(100.00, total_score);
     }
+
+    #[test]
+    fn test_coin_system() {
+        let system = CoinSystem::new(&[5, 2, 5]).unwrap();
+        assert_eq!(system.denominations(), &[2, 5]);
+        assert_eq!(system.min_coins(3), None);
+        assert_eq!(system.make_change(3), None);
+        assert_eq!(system.make_change(11), Some(vec![5, 2, 2, 2]));
+        assert_eq!(system.min_coins(0), Some(0));
+
+        let us = CoinSystem::new(&[1, 5, 10, 25]).unwrap();
+        assert_eq!(us.count_ways(100), 242);
+        assert_eq!(us.make_change(63), Some(vec![25, 25, 10, 1, 1, 1]));
+
+        // 库存有限: 没有 25 时只能用 10 和 5
+        assert_eq!(us.make_change_limited(60, &[10, 2, 5, 0]), Some(vec![10, 10, 10, 10, 10, 5, 5]));
+        assert_eq!(us.make_change_limited(60, &[0, 1, 1, 1]), None);
+        assert_eq!(us.make_change_limited(40, &[3, 2, 1, 1]), Some(vec![25, 10, 5]));
+
+        assert_eq!(CoinSystem::new(&[]), Err(CoinError::Empty));
+        assert_eq!(CoinSystem::new(&[0, 1]), Err(CoinError::ZeroDenomination));
+    }
+
+    #[test]
+    fn test_canonical_coin_system() {
+        use super::canonical::Disagreement;
+
+        let rmb = CoinSystem::new(&[1, 2, 5, 10, 20, 50, 100]).unwrap();
+        assert_eq!(rmb.is_canonical(), Ok(true));
+        assert!(rmb.disagreements(1..=500).is_empty());
+
+        // 加入 30 后依然是规范的, 如 60 = 50 + 10 = 30 + 30
+        let contest = CoinSystem::new(&[1, 2, 5, 10, 20, 30, 50, 100]).unwrap();
+        assert_eq!(contest.greedy_change(93), Some(vec![50, 30, 10, 2, 1]));
+        assert_eq!(contest.is_canonical(), Ok(true));
+        assert!(contest.disagreements(1..=1000).is_empty());
+
+        let uk = CoinSystem::new(&[1, 3, 4]).unwrap();
+        assert_eq!(uk.smallest_counterexample(), Ok(Some(6)));
+        assert_eq!(uk.disagreements(1..=6), vec![Disagreement { amount: 6, greedy: Some(3), optimal: 2 }]);
+
+        let no_unit = CoinSystem::new(&[2, 5]).unwrap();
+        assert_eq!(no_unit.is_canonical(), Err(CoinError::MissingUnitCoin));
+        assert_eq!(no_unit.greedy_change(6), None);
+        assert_eq!(no_unit.disagreements(6..=6), vec![Disagreement { amount: 6, greedy: None, optimal: 3 }]);
+
+        let huge = CoinSystem::new(&[1, 2, u32::MAX - 1, u32::MAX]).unwrap();
+        assert_eq!(huge.smallest_counterexample(), Err(CoinError::SearchOverflow));
+
+        // 不建立与金额同样大的 DP 表
+        assert_eq!(CoinSystem::new(&[1, 3, 4_000_000_000]).unwrap().smallest_counterexample(), Ok(None));
+        let large = CoinSystem::new(&[1, 1_000_000_000, 1_500_000_000]).unwrap();
+        assert_eq!(large.smallest_counterexample(), Ok(Some(2_000_000_000)));
+
+        // 与 Kozen-Zaks 范围内逐个比较 DP 的结果一致
+        for a in 2..16 {
+            for b in a + 1..20 {
+                for c in [None, Some(b + 1), Some(b + 7), Some(2 * b + 1)] {
+                    let coins: Vec<u32> = [1, a, b].into_iter().chain(c).collect();
+                    let system = CoinSystem::new(&coins).unwrap();
+                    let n = coins.len();
+                    let expected = system.disagreements(1..=coins[n - 2] + coins[n - 1]).first().map(|d| d.amount);
+                    assert_eq!(system.smallest_counterexample(), Ok(expected), "{coins:?}");
+                }
+            }
+        }
+    }
 }
-    
\ No newline at end of file
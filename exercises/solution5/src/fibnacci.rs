@@ -1,17 +1,11 @@
-pub fn odd_fibnacci_sum(threshold: u32) -> u32 {
-    // 其实 fibnacci sum 的减半就相当于结果（在最后一位为偶数的情况）
-    let mut prev: u32 = 1;
-    let mut curr: u32 = 1;
-    let mut res = 1; // first one
+use crate::sequence::{fibonacci_sum_below, TermFilter};
 
-    // Something like sliding window, only contains the contents calculating.
-    while curr < threshold {
-        if curr % 2 != 0 {
-            res += curr;
-        }
-        curr += prev;
-        prev = curr - prev;
-    }
-
-    res
+/// 所有小于 threshold 的奇数 Fibonacci 数之和 (F(1) = F(2) = 1 均计入)
+///
+/// 与原实现一致, F(1) 总会计入, threshold <= 1 时结果为 1;
+/// 结果可能超出 u32, 因此返回 u64, 需要更大的范围请直接使用 `sequence::fibonacci_sum_below`
+pub fn odd_fibnacci_sum(threshold: u32) -> u64 {
+    // threshold 不超过 u32::MAX 时和远小于 u64::MAX, 不会返回 None
+    let sum = fibonacci_sum_below(threshold as u128, TermFilter::Odd).unwrap_or_default() as u64;
+    sum.max(1)
 }
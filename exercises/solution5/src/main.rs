@@ -1,7 +1,17 @@
 mod fibnacci;
+mod sequence;
 
 fn main() {
     let num = 20;
     let sum = fibnacci::odd_fibnacci_sum(num);
     println!("{sum}");
+
+    println!("F(186) = {:?}", sequence::fibonacci(186));
+    for filter in [sequence::TermFilter::All, sequence::TermFilter::Even, sequence::TermFilter::DivisibleBy(3)] {
+        println!("{:?} sum below 4e6 = {:?}", filter, sequence::fibonacci_sum_below(4_000_000, filter));
+    }
+    println!("F(10^30) mod 1e9+7 = {}", sequence::fibonacci_mod(10_u128.pow(30), 1_000_000_007));
+    let lucas: Vec<i128> = sequence::LucasSequence::lucas().iter().take(10).collect();
+    println!("lucas: {:?}, L(100) = {:?}", lucas, sequence::LucasSequence::lucas().nth(100));
+    println!("F(150) = {:?}", sequence::LucasSequence::fibonacci().nth(150));
 }
@@ -0,0 +1,242 @@
+//! Fibonacci 数列及一般 Lucas 数列的相关查询
+//!
+//! - 快速倍增法求第 n 项
+//! - 在阈值以下按条件 (奇数, 偶数, 整除) 求和, 全程使用 checked 运算
+//! - 基于 Pisano 周期求 F(n) mod m, n 可以非常大
+//! - 任意系数与初值的 Lucas 数列 x_n = P * x_{n-1} - Q * x_{n-2}
+
+/// 求和时对数列中每一项的筛选条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TermFilter {
+    All,
+    Odd,
+    Even,
+    DivisibleBy(u128),
+}
+
+impl TermFilter {
+    fn accept(&self, term: u128) -> bool {
+        match self {
+            TermFilter::All => true,
+            TermFilter::Odd => term % 2 == 1,
+            TermFilter::Even => term.is_multiple_of(2),
+            TermFilter::DivisibleBy(k) => *k != 0 && term.is_multiple_of(*k),
+        }
+    }
+}
+
+/// 第 n 项 Fibonacci 数 (F(0) = 0, F(1) = 1), 超出 u128 (n > 186) 时返回 None
+pub fn fibonacci(n: u64) -> Option<u128> {
+    let (a, b) = fibonacci_pair(n / 2)?;
+    if n.is_multiple_of(2) {
+        // F(2k) = F(k) * (2F(k+1) - F(k))
+        a.checked_mul(b.checked_mul(2)?.checked_sub(a)?)
+    } else {
+        // F(2k+1) = F(k)^2 + F(k+1)^2
+        a.checked_mul(a)?.checked_add(b.checked_mul(b)?)
+    }
+}
+
+/// (F(k), F(k+1))
+fn fibonacci_pair(k: u64) -> Option<(u128, u128)> {
+    if k == 0 {
+        return Some((0, 1));
+    }
+    let (a, b) = fibonacci_pair(k / 2)?;
+    let even = a.checked_mul(b.checked_mul(2)?.checked_sub(a)?)?;
+    let odd = a.checked_mul(a)?.checked_add(b.checked_mul(b)?)?;
+    if k.is_multiple_of(2) {
+        Some((even, odd))
+    } else {
+        Some((odd, even.checked_add(odd)?))
+    }
+}
+
+/// 从 F(1) 开始所有小于 threshold 且满足条件的 Fibonacci 数之和
+///
+/// 和超出 u128 时返回 None
+pub fn fibonacci_sum_below(threshold: u128, filter: TermFilter) -> Option<u128> {
+    let (mut prev, mut curr): (u128, u128) = (0, 1);
+    let mut res: u128 = 0;
+    while curr < threshold {
+        if filter.accept(curr) {
+            res = res.checked_add(curr)?;
+        }
+        match prev.checked_add(curr) {
+            Some(next) => (prev, curr) = (curr, next),
+            None => break,                      // 下一项已超出 u128, 必然不小于 threshold
+        }
+    }
+    Some(res)
+}
+
+/// F(n) mod m, 先将 n 对 Pisano 周期取模再使用快速倍增
+///
+/// 耗时主要在于计算 Pisano 周期时分解 m, 见 `pisano_period`
+pub fn fibonacci_mod(n: u128, m: u64) -> u64 {
+    if m == 0 { panic!("modulus must be positive") }
+    let n = n % pisano_period(m);
+    fibonacci_pair_mod(n, m).0
+}
+
+/// Pisano 周期 π(m): F(n) mod m 的最小正周期
+///
+/// π(m) = lcm(π(p^k)), 并利用 π(p^k) = p^(k-1) * π(p)
+/// π(p) 为 p - 1 (p ≡ ±1 mod 5) 或 2(p + 1) (p ≡ ±2 mod 5) 的因子
+///
+/// m 与 π(p) 的上界都用试除法分解, 最多试除到平方根;
+/// m 为接近 u64::MAX 的素数 (或两个接近 2^32 的素数之积) 时约需 2^31 次除法, 耗时数秒
+pub fn pisano_period(m: u64) -> u128 {
+    if m == 0 { panic!("modulus must be positive") }
+    factorize(m)
+        .into_iter()
+        .map(|(p, k)| pisano_period_prime(p) * (p as u128).pow(k - 1))
+        .fold(1, lcm)
+}
+
+fn pisano_period_prime(p: u64) -> u128 {
+    match p {
+        2 => 3,
+        5 => 20,
+        _ => {
+            let bound = if p % 5 == 1 || p % 5 == 4 { p as u128 - 1 } else { 2 * (p as u128 + 1) };
+            divisors(bound)
+                .into_iter()
+                .find(|&d| fibonacci_pair_mod(d, p) == (0, 1))
+                .expect("pisano period always divides the bound")
+        }
+    }
+}
+
+/// (F(k) mod m, F(k+1) mod m)
+fn fibonacci_pair_mod(k: u128, m: u64) -> (u64, u64) {
+    let m = m as u128;
+    let mut a: u128 = 0;
+    let mut b: u128 = 1 % m;
+    for bit in (0..128 - k.leading_zeros()).rev() {
+        let even = a * ((2 * b + m - a) % m) % m;
+        let odd = (a * a % m + b * b % m) % m;
+        (a, b) = if (k >> bit) & 1 == 0 { (even, odd) } else { (odd, (even + odd) % m) };
+    }
+    (a as u64, b as u64)
+}
+
+/// 试除法分解质因数
+fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut res = Vec::new();
+    let mut p = 2;
+    while p <= n / p {
+        let mut k = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            k += 1;
+        }
+        if k > 0 {
+            res.push((p, k));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        res.push((n, 1));
+    }
+    res
+}
+
+/// n 的全部因子, 升序
+fn divisors(n: u128) -> Vec<u128> {
+    let mut res = vec![1_u128];
+    // n 不超过 2(u64::MAX + 1), 先提出因子 2 再分解
+    let twos = n.trailing_zeros();
+    let factors = factorize((n >> twos) as u64)
+        .into_iter()
+        .map(|(p, k)| (p as u128, k))
+        .chain((twos > 0).then_some((2, twos)));
+    for (p, k) in factors {
+        let len = res.len();
+        let mut pk = 1;
+        for _ in 0..k {
+            pk *= p;
+            for i in 0..len {
+                res.push(res[i] * pk);
+            }
+        }
+    }
+    res.sort_unstable();
+    res
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn lcm(a: u128, b: u128) -> u128 {
+    a / gcd(a, b) * b
+}
+
+/// 一般 Lucas 数列: x_n = P * x_{n-1} - Q * x_{n-2}, 初值 x_0, x_1 任意
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LucasSequence {
+    pub p: i128,
+    pub q: i128,
+    pub x0: i128,
+    pub x1: i128,
+}
+
+impl LucasSequence {
+    pub fn new(p: i128, q: i128, x0: i128, x1: i128) -> Self {
+        Self { p, q, x0, x1 }
+    }
+
+    /// Fibonacci 数列 U(1, -1)
+    pub fn fibonacci() -> Self {
+        Self::new(1, -1, 0, 1)
+    }
+
+    /// Lucas 数 V(1, -1): 2, 1, 3, 4, 7, ...
+    pub fn lucas() -> Self {
+        Self::new(1, -1, 2, 1)
+    }
+
+    /// 第 n 项, 使用矩阵快速幂, 计算过程溢出时返回 None
+    ///
+    /// [x_{n+1}, x_n]^T = [[P, -Q], [1, 0]]^n * [x_1, x_0]^T
+    pub fn nth(&self, n: u64) -> Option<i128> {
+        let mut result: Matrix = [[1, 0], [0, 1]];
+        let mut base: Matrix = [[self.p, self.q.checked_neg()?], [1, 0]];
+        let mut k = n;
+        while k > 0 {
+            if k & 1 == 1 {
+                result = mat_mul(&result, &base)?;
+            }
+            k >>= 1;
+            if k > 0 {
+                base = mat_mul(&base, &base)?;
+            }
+        }
+        result[1][0].checked_mul(self.x1)?.checked_add(result[1][1].checked_mul(self.x0)?)
+    }
+
+    /// 依次产生 x_0, x_1, ..., 溢出时结束
+    pub fn iter(&self) -> impl Iterator<Item = i128> {
+        let (p, q) = (self.p, self.q);
+        let (mut curr, mut next) = (Some(self.x0), Some(self.x1));
+        std::iter::from_fn(move || {
+            let a = curr?;
+            let after = next.and_then(|b| p.checked_mul(b)?.checked_sub(q.checked_mul(a)?));
+            (curr, next) = (next, after);
+            Some(a)
+        })
+    }
+}
+
+type Matrix = [[i128; 2]; 2];
+
+fn mat_mul(a: &Matrix, b: &Matrix) -> Option<Matrix> {
+    let mut res = [[0_i128; 2]; 2];
+    for i in 0..2 {
+        for j in 0..2 {
+            res[i][j] = a[i][0].checked_mul(b[0][j])?.checked_add(a[i][1].checked_mul(b[1][j])?)?;
+        }
+    }
+    Some(res)
+}
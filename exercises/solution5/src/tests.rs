@@ -1,10 +1,12 @@
 
 // src/tests.rs
 mod fibnacci;
+mod sequence;
 
 #[cfg(test)]
 mod tests {
     use super::fibnacci::odd_fibnacci_sum;
+    use super::sequence::*;
     // 定义测试用例和预期结果
     const TEST_CASES: &[(u32, u32)] = &[
         (20, 23),
@@ -19,12 +21,43 @@ mod tests {
         let mut total_score = 0.0;
         for (input1, expected) in TEST_CASES {
             let result = odd_fibnacci_sum(*input1);
-            if result == *expected {
+            if result == u64::from(*expected) {
                 total_score += 20.0;
             }
         }
         println!("Total score: {:.2}", total_score);
         assert_eq!(100.00, total_score);
     }
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(fibonacci(0), Some(0));
+        assert_eq!(fibonacci(10), Some(55));
+        assert_eq!(fibonacci(186), Some(332825110087067562321196029789634457848));
+        assert_eq!(fibonacci(187), None);
+
+        assert_eq!(fibonacci_sum_below(4_000_000, TermFilter::Even), Some(4613732));
+        assert_eq!(fibonacci_sum_below(100, TermFilter::DivisibleBy(3)), Some(3 + 21));
+        assert_eq!(fibonacci_sum_below(u128::MAX, TermFilter::All), None);
+        // 原实现在 u32 上会溢出
+        assert!(fibonacci_sum_below(u32::MAX as u128, TermFilter::Odd).unwrap() > u32::MAX as u128);
+        assert_eq!(odd_fibnacci_sum(u32::MAX) as u128, fibonacci_sum_below(u32::MAX as u128, TermFilter::Odd).unwrap());
+        assert_eq!(odd_fibnacci_sum(0), 1);
+        assert_eq!(odd_fibnacci_sum(1), 1);
+        assert_eq!(odd_fibnacci_sum(2), 2);
+
+        assert_eq!(pisano_period(10), 60);
+        assert_eq!(pisano_period(1_000_000_007), 2_000_000_016);
+        for n in 0..=186_u64 {
+            assert_eq!(fibonacci_mod(n as u128, 1_000_007) as u128, fibonacci(n).unwrap() % 1_000_007);
+        }
+        assert_eq!(fibonacci_mod(10_u128.pow(30) + 60, 10), fibonacci_mod(10_u128.pow(30), 10));
+        assert_eq!(fibonacci_mod(10_u128.pow(30), 1), 0);
+
+        let lucas: Vec<i128> = LucasSequence::lucas().iter().take(6).collect();
+        assert_eq!(lucas, vec![2, 1, 3, 4, 7, 11]);
+        assert_eq!(LucasSequence::fibonacci().nth(90), fibonacci(90).map(|x| x as i128));
+        // Pell 数: P = 2, Q = -1
+        assert_eq!(LucasSequence::new(2, -1, 0, 1).nth(10), Some(2378));
+    }
 }
-    
\ No newline at end of file
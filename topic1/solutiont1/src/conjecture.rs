@@ -1,102 +1,87 @@
+use std::fmt::{Display, Formatter};
+use number_theory::is_prime;
+
+mod goldbach;
+pub use goldbach::{GoldbachVerifier, StrongReport, WeakReport};
+
 /// 获取前两个不满足 Goldbach 猜想的数之和
 ///
 /// 如果该和存在, 则返回数之和, 否则返回 0
 pub fn goldbach_conjecture() -> u64 {
-    let mut res = 0;
-    // 由 迭代器 中获取奇合数
-    // 这样可以根据需要往后自动拓展
-    for odd in generate_odd_composite_number() {
-        if PRIMES_CACHE.iter().take_while(|&&x| x < odd as u32)    // 所有可能的素数
-            .map(|s| *s as u32 as u64)
-            .all(|p| !((odd - p) % 2 == 0 && is_same_after_sqrt((odd - p) / 2))) {         // (odd - p) / 2 是完全平方数的情况下返回
-
-            // 遍历全部但是还没找到 -> 确实不满足
-            if res == 0 { // 第一个
-                res += odd;
-            } else {
-                res += odd;
-                return res;
-            }
-        }
+    let explorer = GoldbachOther::new(2).expect("multiplier is positive");
+    let found: Vec<u64> = explorer.counterexamples(u64::MAX).take(2).collect();
+    if found.len() == 2 { found.iter().sum() } else { 0 }
+}
 
-    };
-    0
+#[derive(Debug, PartialEq)]
+pub enum ConjectureError {
+    NotOddComposite(u64),       // 输入不是奇合数
+    ZeroMultiplier,             // p + m * k^2 中 m 为 0
+    LimitTooLarge(u64),         // 强/弱猜想的验证范围超出 u32
 }
 
-/// 判断 num 是否是素数
-#[inline(always)]
-fn is_prime(num: u64) -> bool {
-    if num <= 1 {
-        return false;
-    }
-    for i in 2..=((num as f64).sqrt() as u64) {
-        if num % i == 0 {
-            return false;
+impl Display for ConjectureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConjectureError::NotOddComposite(n) => write!(f, "{} is not an odd composite number", n),
+            ConjectureError::ZeroMultiplier => write!(f, "multiplier must be positive"),
+            ConjectureError::LimitTooLarge(limit) => write!(f, "verification limit {} exceeds u32::MAX", limit),
         }
     }
-    true
 }
 
+impl std::error::Error for ConjectureError { }
 
-/// 生成并返回 奇合数 的迭代器
-pub fn generate_odd_composite_number() -> impl Iterator<Item = u64> {
-    let mut cur: u64 = 9;                        // 最小的奇合数
-    std::iter::from_fn(move || loop {
-        if cur % 2 == 1 && !is_prime(cur) {      // 奇数 且 不是素数
-            let res = Some(cur);
-            cur += 2;                            // 检查下一个奇数
-            return res;
-        }
-        cur += 2
-    })
-}
-
-/// 精度更好的开方匹配
-/// odd = primes + 2 * n.pow(2)
-/// n = $\left (\sqrt{\frac{odd - primes}{2} } \right ) ^2$
-#[inline]
-pub fn is_same_after_sqrt(num: u64) -> bool {
-    // f64::sqrt(num as f64) % 1_f64 == 0_f64 // TODO： 精度可能不是很高
-    let tmp = (num as f64).sqrt() as u64;
-    tmp.pow(2) == num
+/// Goldbach 的 "另一个猜想": 每个奇合数都能写成 p + m * k^2 (p 为素数, k >= 1)
+///
+/// 原猜想对应 m = 2, 已知的反例只有 5777 和 5993
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoldbachOther {
+    m: u64,
 }
 
-/*
-    下面的文件是历史实现版本所引用的, 与当前实现无关
- */
+impl GoldbachOther {
+    pub fn new(m: u64) -> Result<Self, ConjectureError> {
+        if m == 0 {
+            return Err(ConjectureError::ZeroMultiplier);
+        }
+        Ok(Self { m })
+    }
 
-/// 计算一个数能否由一个素数与其平方的两倍计算而成
-/// n: 奇合数
-#[deprecated]
-pub fn goldbach_falsification(odd: u32) -> bool {
-    for prime in PRIMES_CACHE.iter().take_while(|&&x| x <= (odd / 2) as u32) {
-        if is_same_after_sqrt(((odd - prime) / 2) as u64) {
-            return true;
+    /// 返回 k 最小的分解 (p, k), 使得 n = p + m * k^2
+    ///
+    /// 穷举全部 m * k^2 < n 之后仍未找到时返回 Ok(None), 即 n 是一个反例
+    pub fn witness(&self, n: u64) -> Result<Option<(u64, u64)>, ConjectureError> {
+        if n.is_multiple_of(2) || n < 9 || is_prime(n) {
+            return Err(ConjectureError::NotOddComposite(n));
         }
+        Ok(self.find_witness(n))
     }
-    false
-}
 
-/// 计算某个数是不是奇合数
-/// 计算公式为所有正因子的总和相当于两倍的该数字
-#[deprecated]
-pub fn is_odd_composite_number(n: u32) -> bool {
-    find_poss_factors(n).iter().sum::<u32>() == 2 * n
-}
+    fn find_witness(&self, n: u64) -> Option<(u64, u64)> {
+        (1_u64..)
+            .map_while(|k| self.m.checked_mul(k.checked_mul(k)?).filter(|&sq| sq < n).map(|sq| (k, sq)))
+            .find(|&(_, sq)| is_prime(n - sq))
+            .map(|(k, sq)| (n - sq, k))
+    }
 
-/// 正因子计算
-#[deprecated]
-pub fn find_poss_factors(n: u32) -> Vec<u32> {
-    let mut factors = Vec::new();
-    let limit = (n as f32).sqrt() as u32;
+    /// 不超过 bound 的全部反例, 按升序惰性产生
+    pub fn counterexamples(&self, bound: u64) -> impl Iterator<Item = u64> {
+        let explorer = *self;
+        generate_odd_composite_number()
+            .take_while(move |&odd| odd <= bound)
+            .filter(move |&odd| explorer.find_witness(odd).is_none())
+    }
+}
 
-    for i in 1..=limit {
-        if n % i == 0 {
-            factors.push(i);
-            if i != n / i {
-                factors.push(n / i); // 添加配对的因子
-            }
+/// 生成并返回 奇合数 的迭代器
+pub fn generate_odd_composite_number() -> impl Iterator<Item = u64> {
+    let mut cur: Option<u64> = Some(9);          // 最小的奇合数
+    std::iter::from_fn(move || loop {
+        let odd = cur?;
+        cur = odd.checked_add(2);                // 检查下一个奇数, 到达 u64 上界时结束
+        if !is_prime(odd) {                      // 奇数 且 不是素数
+            return Some(odd);
         }
-    }
-    factors
-}
\ No newline at end of file
+    })
+}
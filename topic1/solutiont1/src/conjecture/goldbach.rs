@@ -0,0 +1,220 @@
+//! Goldbach 猜想 (强/弱) 在给定范围内的验证
+//!
+//! - 强猜想: 每个大于 2 的偶数都可以写成两个素数之和
+//! - 弱猜想: 每个大于 5 的奇数都可以写成三个素数之和
+//!
+//! 待验证的偶数 (或奇数) 按区间分块后交由多个线程并行处理, 每完成一块调用一次进度回调;
+//! 分解中的素数以 u32 存放, 因此 limit 不能超过 u32::MAX
+use std::sync::atomic::{AtomicU64, Ordering};
+use number_theory::Sieve;
+use super::ConjectureError;
+
+/// 每个线程一次领取的数的个数
+const CHUNK_SIZE: usize = 1 << 14;
+
+/// 强猜想的验证结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrongReport {
+    pub limit: u64,
+    /// min_prime[n / 2]: 偶数 n 的分解中最小的素数, 0 表示无法分解 (或 n < 4)
+    min_prime: Vec<u32>,
+    /// 无法分解的偶数, 若猜想成立则为空
+    pub counterexamples: Vec<u64>,
+}
+
+impl StrongReport {
+    /// 偶数 n 的最小素数分解 (p, n - p), p <= n - p
+    pub fn decomposition(&self, n: u64) -> Option<(u64, u64)> {
+        if n % 2 == 1 || n > self.limit {
+            return None;
+        }
+        match self.min_prime.get((n / 2) as usize) {
+            Some(&p) if p > 0 => Some((p as u64, n - p as u64)),
+            _ => None,
+        }
+    }
+}
+
+/// 弱猜想的验证结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeakReport {
+    pub limit: u64,
+    /// decompositions[(n - 7) / 2]: 奇数 n 的分解 (p1, p2, p3), p1 尽可能小且 p2 <= p3
+    decompositions: Vec<(u32, u32, u32)>,
+    pub counterexamples: Vec<u64>,
+}
+
+impl WeakReport {
+    pub fn decomposition(&self, n: u64) -> Option<(u64, u64, u64)> {
+        if n.is_multiple_of(2) || n < 7 || n > self.limit {
+            return None;
+        }
+        match self.decompositions.get(((n - 7) / 2) as usize) {
+            Some(&(a, b, c)) if a > 0 => Some((a as u64, b as u64, c as u64)),
+            _ => None,
+        }
+    }
+}
+
+/// 在 [0, limit] 内验证 Goldbach 猜想
+///
+/// # 示例:
+/// ```rust
+/// let report = GoldbachVerifier::new(1_000_000)?
+///     .threads(4)
+///     .verify_strong(|done, total| println!("{done}/{total}"));
+/// assert!(report.counterexamples.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct GoldbachVerifier {
+    limit: u64,
+    threads: usize,
+}
+
+impl GoldbachVerifier {
+    /// 默认使用全部可用的 CPU 核心, limit 超过 u32::MAX 时返回错误
+    pub fn new(limit: u64) -> Result<Self, ConjectureError> {
+        if limit > u32::MAX as u64 {
+            return Err(ConjectureError::LimitTooLarge(limit));
+        }
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Ok(Self { limit, threads })
+    }
+
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// 验证强猜想, 对每个偶数记录最小素数分解
+    ///
+    /// progress(done, total): 已完成与总共的偶数个数
+    pub fn verify_strong(&self, progress: impl Fn(u64, u64) + Sync) -> StrongReport {
+        let sieve = Sieve::new(self.limit);
+        let primes: Vec<u32> = sieve.primes().map(|p| p as u32).collect();
+        let total = self.even_count() as u64;
+        self.strong_report(&sieve, &primes, &progress, (0, total))
+    }
+
+    /// 验证弱猜想, 借助强猜想的结果: 对奇数 n 找到最小的 p1 使 n - p1 可以写成两素数之和
+    ///
+    /// progress(done, total): 两个阶段合计已完成与总共的数的个数, 先验证全部偶数, 再验证奇数
+    pub fn verify_weak(&self, progress: impl Fn(u64, u64) + Sync) -> WeakReport {
+        let sieve = Sieve::new(self.limit);
+        let primes: Vec<u32> = sieve.primes().map(|p| p as u32).collect();
+        let evens = self.even_count() as u64;
+        let odds = if self.limit >= 7 { ((self.limit - 7) / 2 + 1) as usize } else { 0 };
+        let total = evens + odds as u64;
+        let strong = self.strong_report(&sieve, &primes, &progress, (0, total));
+
+        let decompositions = self.for_each_chunk(odds, &progress, (evens, total), |i| {
+            let n = 7 + 2 * i as u64;
+            primes.iter()
+                .map(|&p| p as u64)
+                .take_while(|&p| p + 4 <= n)
+                .find_map(|p| {
+                    let rest = n - p;
+                    if rest.is_multiple_of(2) {
+                        strong.decomposition(rest).map(|(a, b)| (p, a, b))
+                    } else {
+                        // 奇数只能拆为 2 + 奇素数
+                        sieve.is_prime(rest - 2).then_some((p, 2, rest - 2))
+                    }
+                })
+                .map_or((0, 0, 0), |(a, b, c)| (a as u32, b as u32, c as u32))
+        });
+        let counterexamples = decompositions.iter()
+            .enumerate()
+            .filter(|(_, &(a, _, _))| a == 0)
+            .map(|(i, _)| 7 + 2 * i as u64)
+            .collect();
+        WeakReport { limit: self.limit, decompositions, counterexamples }
+    }
+
+    /// Goldbach 彗星: counts[n / 2] 为偶数 n 的分解方式数 (p <= q, 不计顺序)
+    ///
+    /// 复杂度约为 O(limit * π(limit)), 适合 10^6 以内的范围
+    pub fn comet(&self, progress: impl Fn(u64, u64) + Sync) -> Vec<u32> {
+        let sieve = Sieve::new(self.limit);
+        let primes: Vec<u32> = sieve.primes().map(|p| p as u32).collect();
+        let total = self.even_count() as u64;
+        self.for_each_chunk(self.even_count(), &progress, (0, total), |i| {
+            let n = 2 * i as u64;
+            if n < 4 {
+                return 0;
+            }
+            primes.iter()
+                .take_while(|&&p| p as u64 <= n / 2)
+                .filter(|&&p| sieve.is_prime(n - p as u64))
+                .count() as u32
+        })
+    }
+
+    /// [0, limit] 内偶数的个数
+    fn even_count(&self) -> usize {
+        (self.limit / 2 + 1) as usize
+    }
+
+    /// 并行求出每个偶数的最小素数分解, stage 为进度回调的 (起始值, 总数)
+    fn strong_report(
+        &self,
+        sieve: &Sieve,
+        primes: &[u32],
+        progress: &(impl Fn(u64, u64) + Sync),
+        stage: (u64, u64),
+    ) -> StrongReport {
+        let min_prime = self.for_each_chunk(self.even_count(), progress, stage, |i| {
+            let n = 2 * i as u64;
+            if n < 4 {
+                return 0;
+            }
+            primes.iter()
+                .take_while(|&&p| p as u64 <= n / 2)
+                .find(|&&p| sieve.is_prime(n - p as u64))
+                .copied()
+                .unwrap_or(0)
+        });
+        let counterexamples = (2..min_prime.len())
+            .filter(|&i| min_prime[i] == 0)
+            .map(|i| 2 * i as u64)
+            .collect();
+        StrongReport { limit: self.limit, min_prime, counterexamples }
+    }
+
+    /// 对 0..len 内的每个下标 i 并行计算 f(i)
+    ///
+    /// 每完成一块调用 progress(offset + 已完成的个数, total), stage = (offset, total)
+    fn for_each_chunk<T, F>(&self, len: usize, progress: &(impl Fn(u64, u64) + Sync), stage: (u64, u64), f: F) -> Vec<T>
+    where
+        T: Copy + Default + Send,
+        F: Fn(usize) -> T + Sync,
+    {
+        let (offset, total) = stage;
+        let mut res = vec![T::default(); len];
+        let done = AtomicU64::new(0);
+
+        let chunks: Vec<(usize, &mut [T])> = res.chunks_mut(CHUNK_SIZE).enumerate().collect();
+        let mut groups: Vec<Vec<(usize, &mut [T])>> = (0..self.threads).map(|_| Vec::new()).collect();
+        // 轮流分配, 使各线程负责的数大小相近
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            groups[i % self.threads].push(chunk);
+        }
+
+        std::thread::scope(|scope| {
+            for group in groups {
+                let (f, done) = (&f, &done);
+                scope.spawn(move || {
+                    for (idx, chunk) in group {
+                        let base = idx * CHUNK_SIZE;
+                        for (pos, slot) in chunk.iter_mut().enumerate() {
+                            *slot = f(base + pos);
+                        }
+                        let finished = done.fetch_add(chunk.len() as u64, Ordering::Relaxed) + chunk.len() as u64;
+                        progress(offset + finished, total);
+                    }
+                });
+            }
+        });
+        res
+    }
+}
@@ -3,4 +3,22 @@ mod conjecture;
 fn main() {
     let sum = conjecture::goldbach_conjecture();
     println!("top 10 goldbach's conjecture on primes: {sum}");
+
+    // p + m * k^2 的推广
+    for m in 1..=4 {
+        let explorer = conjecture::GoldbachOther::new(m).unwrap();
+        let found: Vec<u64> = explorer.counterexamples(10_000).take(10).collect();
+        println!("m = {m}: {found:?}, 9999 = {:?}", explorer.witness(9999));
+    }
+
+    // 强/弱 Goldbach 猜想验证
+    let verifier = conjecture::GoldbachVerifier::new(1_000_000).unwrap();
+    let strong: conjecture::StrongReport = verifier.verify_strong(|done, total| {
+        if done == total { println!("strong goldbach: {done}/{total} checked") }
+    });
+    let weak: conjecture::WeakReport = verifier.verify_weak(|_, _| {});
+    println!("strong counterexamples: {:?}, 1000000 = {:?}", strong.counterexamples, strong.decomposition(1_000_000));
+    println!("weak counterexamples: {:?}, 999999 = {:?}", weak.counterexamples, weak.decomposition(999_999));
+    let comet = conjecture::GoldbachVerifier::new(100).unwrap().threads(1).comet(|_, _| {});
+    println!("goldbach comet up to 100: {:?}", &comet[2..]);
 }
@@ -3,7 +3,9 @@ mod conjecture;
 
 #[cfg(test)]
 mod tests {
-    use super::conjecture::goldbach_conjecture;
+    use super::conjecture::{
+        goldbach_conjecture, ConjectureError, GoldbachOther, GoldbachVerifier, StrongReport, WeakReport,
+    };
     use std::time::{Instant, Duration};
 
     // 定义测试用例和预期结果
@@ -26,4 +28,59 @@ mod tests {
         println!("Total score: {:.2}", total_score);
         assert_eq!(100.00, total_score);
     }
+
+    #[test]
+    fn test_goldbach_other_explorer() {
+        let classic = GoldbachOther::new(2).unwrap();
+        assert_eq!(classic.witness(9), Ok(Some((7, 1))));
+        assert_eq!(classic.witness(33), Ok(Some((31, 1))));
+        assert_eq!(classic.witness(5777), Ok(None));
+        assert_eq!(classic.witness(7), Err(ConjectureError::NotOddComposite(7)));
+        assert_eq!(classic.witness(10), Err(ConjectureError::NotOddComposite(10)));
+        assert_eq!(classic.counterexamples(100_000).collect::<Vec<_>>(), vec![5777, 5993]);
+
+        // p + k^2 存在大量反例, 如 25 - k^2 = 24, 21, 16, 9 均不是素数
+        let square = GoldbachOther::new(1).unwrap();
+        assert_eq!(square.witness(25), Ok(None));
+        assert_eq!(square.witness(27), Ok(Some((23, 2))));
+        assert!(square.counterexamples(1000).all(|n| square.witness(n) == Ok(None)));
+        assert_eq!(GoldbachOther::new(0), Err(ConjectureError::ZeroMultiplier));
+    }
+
+    #[test]
+    fn test_goldbach_verification() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let last = AtomicU64::new(0);
+        let verifier = GoldbachVerifier::new(100_000).unwrap().threads(3);
+        let strong: StrongReport = verifier.verify_strong(|done, total| {
+            last.fetch_max(done, Ordering::Relaxed);
+            assert!(done <= total);
+        });
+        assert_eq!(last.load(Ordering::Relaxed), 50_001);
+        assert!(strong.counterexamples.is_empty());
+        assert_eq!(strong.decomposition(4), Some((2, 2)));
+        assert_eq!(strong.decomposition(98), Some((19, 79)));
+        assert_eq!(strong.decomposition(99), None);
+
+        // 弱猜想的进度包含偶数与奇数两个阶段: 50001 + 49997
+        last.store(0, Ordering::Relaxed);
+        let weak: WeakReport = verifier.verify_weak(|done, total| {
+            last.fetch_max(done, Ordering::Relaxed);
+            assert!(done <= total);
+        });
+        assert_eq!(last.load(Ordering::Relaxed), 99_998);
+        assert!(weak.counterexamples.is_empty());
+        assert_eq!(weak.decomposition(7), Some((2, 2, 3)));
+        assert_eq!(weak.decomposition(99_999).map(|(a, b, c)| a + b + c), Some(99_999));
+
+        // 彗星数据: 与单线程结果一致
+        let comet = GoldbachVerifier::new(1000).unwrap().threads(4).comet(|_, _| {});
+        assert_eq!(comet, GoldbachVerifier::new(1000).unwrap().threads(1).comet(|_, _| {}));
+        assert_eq!(comet[2], 1);         // 4 = 2 + 2
+        assert_eq!(comet[5], 2);         // 10 = 3 + 7 = 5 + 5
+        assert_eq!(comet[50], 6);        // 100
+
+        assert_eq!(GoldbachVerifier::new(1 << 32).err(), Some(ConjectureError::LimitTooLarge(1 << 32)));
+    }
 }
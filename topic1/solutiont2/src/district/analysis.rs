@@ -0,0 +1,232 @@
+//! 城市图的进一步分析
+//!
+//! # 主要特点:
+//! - 连通块 (省份) 列表与其中最大的一个
+//! - 桥 (拆除后会使省份分裂的道路) 与割点 (移除后会使省份分裂的城市), Tarjan 算法, 迭代实现
+//! - 两个城市之间经过道路数最少的路径, 广度优先搜索
+//!
+//! 道路视为无向且不重复的集合, 邻接表中两个方向各出现一次的道路只算一条, 自环被忽略
+//!
+//! # 参考文献:
+//! - https://cp-algorithms.com/graph/bridge-searching.html
+//! - https://cp-algorithms.com/graph/cutpoints.html
+use std::collections::VecDeque;
+use serde::Serialize;
+use crate::district::interner::Interner;
+use crate::district::loader::Graph;
+use crate::district::union_find::DSU;
+use crate::district::intern_roads;
+
+const UNVISITED: usize = usize::MAX;
+
+/// 以编号表示的城市图
+#[derive(Debug, Clone)]
+pub struct CityGraph<'a> {
+    cities: Interner<'a>,
+    /// 去重后的道路 (u, v), u < v, 按编号升序
+    roads: Vec<(usize, usize)>,
+    /// adj[u]: (相邻城市, 道路编号)
+    adj: Vec<Vec<(usize, usize)>>,
+}
+
+/// 一个批次的分析结果, 可直接序列化为 JSON
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Analysis {
+    pub name: String,
+    pub cities: usize,
+    pub roads: usize,
+    pub provinces: usize,
+    pub components: Vec<Vec<String>>,
+    pub largest_component: Vec<String>,
+    pub bridges: Vec<(String, String)>,
+    pub articulation_points: Vec<String>,
+}
+
+impl<'a> CityGraph<'a> {
+    pub fn new(graph: &'a Graph) -> Self {
+        let (cities, roads) = intern_roads(graph);
+        let mut roads: Vec<(usize, usize)> = roads.into_iter()
+            .filter(|&(u, v)| u != v)
+            .map(|(u, v)| (u.min(v), u.max(v)))
+            .collect();
+        roads.sort_unstable();
+        roads.dedup();
+
+        let mut adj = vec![Vec::new(); cities.len()];
+        for (id, &(u, v)) in roads.iter().enumerate() {
+            adj[u].push((v, id));
+            adj[v].push((u, id));
+        }
+        CityGraph { cities, roads, adj }
+    }
+
+    pub fn city_count(&self) -> usize {
+        self.cities.len()
+    }
+
+    pub fn road_count(&self) -> usize {
+        self.roads.len()
+    }
+
+    /// 全部连通块, 块与块内城市均按首次出现的顺序排列
+    pub fn components(&self) -> Vec<Vec<&'a str>> {
+        let mut union_find = self.union_find();
+        let mut seen = vec![false; self.city_count()];
+        let mut res: Vec<Vec<&'a str>> = Vec::with_capacity(union_find.count_sets());
+        for idx in 0..self.city_count() {
+            let root = union_find.find(idx);
+            if !seen[root] {
+                seen[root] = true;
+                let mut members = union_find.metadata(root).clone();
+                members.sort_unstable();
+                res.push(members.into_iter().map(|idx| self.cities.resolve(idx)).collect());
+            }
+        }
+        res
+    }
+
+    /// 城市数最多的连通块, 有多个时取最早出现的一个, 图为空时返回空列表
+    pub fn largest_component(&self) -> Vec<&'a str> {
+        let mut union_find = self.union_find();
+        let Some(best) = (0..self.city_count()).rev().max_by_key(|&idx| union_find.size(idx)) else {
+            return Vec::new();
+        };
+        (0..self.city_count())
+            .filter(|&idx| union_find.same(idx, best))
+            .map(|idx| self.cities.resolve(idx))
+            .collect()
+    }
+
+    /// 桥, 按道路两端城市的首次出现顺序排列
+    pub fn bridges(&self) -> Vec<(&'a str, &'a str)> {
+        let (bridges, _) = self.low_links();
+        bridges.into_iter()
+            .map(|id| (self.cities.resolve(self.roads[id].0), self.cities.resolve(self.roads[id].1)))
+            .collect()
+    }
+
+    /// 割点, 按首次出现的顺序排列
+    pub fn articulation_points(&self) -> Vec<&'a str> {
+        let (_, cut) = self.low_links();
+        (0..self.city_count())
+            .filter(|&idx| cut[idx])
+            .map(|idx| self.cities.resolve(idx))
+            .collect()
+    }
+
+    /// from 到 to 经过道路数最少的路径 (包括两端), 任一城市不存在或不连通时返回 None
+    pub fn shortest_path(&self, from: &str, to: &str) -> Option<Vec<&'a str>> {
+        let (from, to) = (self.cities.get(from)?, self.cities.get(to)?);
+        let mut prev = vec![UNVISITED; self.city_count()];
+        let mut queue = VecDeque::from([from]);
+        prev[from] = from;
+        while let Some(u) = queue.pop_front() {
+            if u == to {
+                break;
+            }
+            for &(v, _) in &self.adj[u] {
+                if prev[v] == UNVISITED {
+                    prev[v] = u;
+                    queue.push_back(v);
+                }
+            }
+        }
+        if prev[to] == UNVISITED {
+            return None;
+        }
+
+        let mut path = vec![self.cities.resolve(to)];
+        let mut curr = to;
+        while curr != from {
+            curr = prev[curr];
+            path.push(self.cities.resolve(curr));
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn analyze(&self, name: &str) -> Analysis {
+        let owned = |cities: Vec<&str>| cities.into_iter().map(str::to_string).collect::<Vec<String>>();
+        let components: Vec<Vec<String>> = self.components().into_iter().map(owned).collect();
+        Analysis {
+            name: name.to_string(),
+            cities: self.city_count(),
+            roads: self.road_count(),
+            provinces: components.len(),
+            components,
+            largest_component: owned(self.largest_component()),
+            bridges: self.bridges().into_iter().map(|(u, v)| (u.to_string(), v.to_string())).collect(),
+            articulation_points: owned(self.articulation_points()),
+        }
+    }
+
+    /// 以道路连接城市的并查集, 每个集合携带其中城市的编号
+    fn union_find(&self) -> DSU<Vec<usize>> {
+        let mut union_find = DSU::with_metadata((0..self.city_count()).map(|idx| vec![idx]).collect());
+        for &(u, v) in &self.roads {
+            union_find.union(u, v);
+        }
+        union_find
+    }
+
+    /// Tarjan 算法, 返回 (桥的道路编号, 每个城市是否为割点)
+    ///
+    /// tin[v]: 深度优先搜索中首次访问 v 的时刻
+    /// low[v]: v 的子树中经过至多一条返祖边能到达的最小 tin
+    fn low_links(&self) -> (Vec<usize>, Vec<bool>) {
+        let n = self.city_count();
+        let mut tin = vec![UNVISITED; n];
+        let mut low = vec![0_usize; n];
+        let mut cut = vec![false; n];
+        let mut bridges = Vec::new();
+        let mut timer = 0;
+
+        for root in 0..n {
+            if tin[root] != UNVISITED {
+                continue;
+            }
+            tin[root] = timer;
+            low[root] = timer;
+            timer += 1;
+            let mut root_children = 0;
+            // (城市, 进入该城市所经过的道路, 下一个要检查的邻居下标)
+            let mut stack: Vec<(usize, usize, usize)> = vec![(root, UNVISITED, 0)];
+
+            while let Some((v, parent_road, next)) = stack.last_mut() {
+                let v = *v;
+                if let Some(&(to, road)) = self.adj[v].get(*next) {
+                    *next += 1;
+                    if road == *parent_road {
+                        continue;
+                    }
+                    if tin[to] != UNVISITED {
+                        low[v] = low[v].min(tin[to]);
+                    } else {
+                        tin[to] = timer;
+                        low[to] = timer;
+                        timer += 1;
+                        stack.push((to, road, 0));
+                    }
+                    continue;
+                }
+
+                let parent_road = *parent_road;
+                stack.pop();
+                if let Some(&(p, _, _)) = stack.last() {
+                    low[p] = low[p].min(low[v]);
+                    if low[v] > tin[p] {
+                        bridges.push(parent_road);
+                    }
+                    if p == root {
+                        root_children += 1;
+                    } else if low[v] >= tin[p] {
+                        cut[p] = true;
+                    }
+                }
+            }
+            cut[root] = root_children > 1;
+        }
+        bridges.sort_unstable();
+        (bridges, cut)
+    }
+}
@@ -0,0 +1,210 @@
+//! 道路增删过程中的动态连通性 (离线)
+//!
+//! # 变更日志格式:
+//! 每行一条变更, `#` 之后为注释, 城市名中不能含有空白
+//! ```text
+//! + 成都 深圳      # 新建道路
+//! - 成都 深圳      # 拆除道路
+//! + 三亚           # 新增一个孤立的城市
+//! ```
+//!
+//! # 实现原理:
+//! - 道路为无向且不重复的集合, 每条道路在时间轴上存在于若干个区间 [建立, 拆除)
+//! - 将这些区间挂到时间轴的线段树上, 深度优先遍历线段树:
+//!   进入节点时把区间内的道路并入 RollbackDSU, 离开时撤销, 到达叶子时记录连通块个数
+//! - 总复杂度 O((n + q) log q log n)
+//!
+//! # 参考文献:
+//! - https://cp-algorithms.com/data_structures/deleting_in_log_n.html
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use crate::district::interner::Interner;
+use crate::district::loader::Graph;
+use crate::district::union_find::RollbackDSU;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    AddRoad(String, String),
+    RemoveRoad(String, String),
+    AddCity(String),
+}
+
+/// 变更日志中的一行, line 从 1 开始
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub line: usize,
+    pub change: Change,
+}
+
+#[derive(Debug)]
+pub enum ChangeLogError {
+    Io(std::io::Error),                                         // 读取文件失败
+    Syntax { line: usize, message: String },                    // 无法解析的行
+    MissingRoad { line: usize, from: String, to: String },      // 拆除不存在的道路
+    DuplicateRoad { line: usize, from: String, to: String },    // 重复建立已有的道路
+}
+
+impl Display for ChangeLogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeLogError::Io(e) => write!(f, "io error: {e}"),
+            ChangeLogError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            ChangeLogError::MissingRoad { line, from, to } => write!(f, "line {line}: road {from} - {to} does not exist"),
+            ChangeLogError::DuplicateRoad { line, from, to } => write!(f, "line {line}: road {from} - {to} already exists"),
+        }
+    }
+}
+
+impl std::error::Error for ChangeLogError { }
+
+impl From<std::io::Error> for ChangeLogError {
+    fn from(e: std::io::Error) -> Self {
+        ChangeLogError::Io(e)
+    }
+}
+
+pub fn load_change_log(path: impl AsRef<Path>) -> Result<Vec<LogEntry>, ChangeLogError> {
+    parse_change_log(BufReader::new(File::open(path)?))
+}
+
+pub fn parse_change_log(reader: impl Read) -> Result<Vec<LogEntry>, ChangeLogError> {
+    let mut res = Vec::new();
+    for (idx, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let content = line.split('#').next().unwrap_or_default();
+        let mut words = content.split_whitespace();
+        let Some(op) = words.next() else { continue };
+        let cities: Vec<&str> = words.collect();
+
+        let change = match (op, cities.as_slice()) {
+            ("+", [city]) => Change::AddCity(city.to_string()),
+            ("+", [from, to]) => Change::AddRoad(from.to_string(), to.to_string()),
+            ("-", [from, to]) => Change::RemoveRoad(from.to_string(), to.to_string()),
+            ("+", _) => return Err(ChangeLogError::Syntax {
+                line: idx + 1,
+                message: format!("'+' expects 1 or 2 cities, found {}", cities.len()),
+            }),
+            ("-", _) => return Err(ChangeLogError::Syntax {
+                line: idx + 1,
+                message: format!("'-' expects 2 cities, found {}", cities.len()),
+            }),
+            _ => return Err(ChangeLogError::Syntax { line: idx + 1, message: format!("unknown operation '{op}'") }),
+        };
+        res.push(LogEntry { line: idx + 1, change });
+    }
+    Ok(res)
+}
+
+/// 以 initial 为初始状态依次执行 changes
+///
+/// 返回长度为 changes.len() + 1 的连通块个数, 第 0 项为初始状态, 第 i 项为执行第 i 条变更之后;
+/// 尚未出现过的城市不参与计数
+pub fn replay(initial: &Graph, changes: &[LogEntry]) -> Result<Vec<usize>, ChangeLogError> {
+    let steps = changes.len() + 1;
+    let mut ids = Interner::default();
+    // appear[t]: 在时刻 t 首次出现的城市数量
+    let mut appear = vec![0_usize; steps];
+
+    // 当前存在的道路 (u, v), u <= v, 值为建立的时刻
+    let mut alive: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut intervals: Vec<(usize, usize, (usize, usize))> = Vec::new();
+    for (city, neighbors) in initial.adjacency.iter() {
+        let u = intern(&mut ids, city, 0, &mut appear);
+        for neighbor in neighbors {
+            let v = intern(&mut ids, neighbor, 0, &mut appear);
+            alive.entry((u.min(v), u.max(v))).or_insert(0);
+        }
+    }
+
+    for (idx, entry) in changes.iter().enumerate() {
+        let t = idx + 1;
+        match &entry.change {
+            Change::AddCity(city) => {
+                intern(&mut ids, city, t, &mut appear);
+            }
+            Change::AddRoad(from, to) => {
+                let (u, v) = (intern(&mut ids, from, t, &mut appear), intern(&mut ids, to, t, &mut appear));
+                if alive.insert((u.min(v), u.max(v)), t).is_some() {
+                    return Err(ChangeLogError::DuplicateRoad { line: entry.line, from: from.clone(), to: to.clone() });
+                }
+            }
+            Change::RemoveRoad(from, to) => {
+                let key = ids.get(from).zip(ids.get(to)).map(|(u, v)| (u.min(v), u.max(v)));
+                match key.and_then(|key| alive.remove(&key).map(|start| (start, key))) {
+                    Some((start, key)) => intervals.push((start, t, key)),
+                    None => return Err(ChangeLogError::MissingRoad { line: entry.line, from: from.clone(), to: to.clone() }),
+                }
+            }
+        }
+    }
+    intervals.extend(alive.into_iter().map(|(key, start)| (start, steps, key)));
+
+    let mut tree = SegmentTree { roads: vec![Vec::new(); 4 * steps] };
+    for (start, end, road) in intervals {
+        if road.0 != road.1 && start < end {
+            tree.insert(1, 0, steps, start, end, road);
+        }
+    }
+
+    // unseen[t]: 到时刻 t 为止仍未出现的城市数量, 它们在 DSU 中各自单独成为一个集合
+    let mut rest = ids.len();
+    let unseen: Vec<usize> = appear.iter().map(|&n| {
+        rest -= n;
+        rest
+    }).collect();
+
+    let mut dsu = RollbackDSU::with_capacity(ids.len());
+    let mut counts = vec![0_usize; steps];
+    tree.solve(1, 0, steps, &mut dsu, &mut |t, dsu| counts[t] = dsu.count_sets() - unseen[t]);
+    Ok(counts)
+}
+
+/// 获取城市的编号, 首次出现时分配新的编号并记录出现的时刻
+fn intern<'a>(ids: &mut Interner<'a>, city: &'a str, t: usize, appear: &mut [usize]) -> usize {
+    let len = ids.len();
+    let id = ids.intern(city);
+    if id == len {
+        appear[t] += 1;
+    }
+    id
+}
+
+/// 时间轴 [0, len) 上的线段树, 每个节点保存完整覆盖该节点区间的道路
+struct SegmentTree {
+    roads: Vec<Vec<(usize, usize)>>,
+}
+
+impl SegmentTree {
+    /// 将在 [start, end) 内存在的 road 挂到节点 node (对应区间 [lo, hi)) 之下
+    fn insert(&mut self, node: usize, lo: usize, hi: usize, start: usize, end: usize, road: (usize, usize)) {
+        if start <= lo && hi <= end {
+            self.roads[node].push(road);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        if start < mid {
+            self.insert(2 * node, lo, mid, start, end, road);
+        }
+        if mid < end {
+            self.insert(2 * node + 1, mid, hi, start, end, road);
+        }
+    }
+
+    fn solve(&self, node: usize, lo: usize, hi: usize, dsu: &mut RollbackDSU, visit: &mut impl FnMut(usize, &RollbackDSU)) {
+        let snapshot = dsu.snapshot();
+        for &(u, v) in &self.roads[node] {
+            dsu.union(u, v);
+        }
+        if hi - lo == 1 {
+            visit(lo, dsu);
+        } else {
+            let mid = (lo + hi) / 2;
+            self.solve(2 * node, lo, mid, dsu, visit);
+            self.solve(2 * node + 1, mid, hi, dsu, visit);
+        }
+        dsu.rollback(snapshot);
+    }
+}
@@ -0,0 +1,20 @@
+//! 生成用于性能测试的随机城市图
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::district::loader::Graph;
+
+/// 含有 cities 个城市与 roads 条随机道路的单个批次, 相同的 seed 生成相同的图
+///
+/// 每个城市都会作为邻接表的 key 出现 (没有道路时邻居列表为空), 因此城市总数恰为 cities
+pub fn random_graph(cities: usize, roads: usize, seed: u64) -> Graph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let names: Vec<String> = (0..cities).map(|i| format!("城市{i}")).collect();
+    let mut adjacency: Vec<(String, Vec<String>)> = names.iter().map(|name| (name.clone(), Vec::new())).collect();
+    if cities > 0 {
+        for _ in 0..roads {
+            let (u, v) = (rng.gen_range(0..cities), rng.gen_range(0..cities));
+            adjacency[u].1.push(names[v].clone());
+        }
+    }
+    Graph { name: format!("random-{cities}-{roads}-{seed}"), adjacency }
+}
@@ -0,0 +1,46 @@
+//! 字符串驻留
+//!
+//! 将城市名映射为从 0 开始的连续编号, 编号即首次出现的顺序
+//! - intern / get 为 O(1) (哈希), resolve 为 O(1) (下标)
+//! - 只保存对原字符串的引用, 不复制城市名
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct Interner<'a> {
+    ids: HashMap<&'a str, usize>,
+    names: Vec<&'a str>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Interner { ids: HashMap::with_capacity(capacity), names: Vec::with_capacity(capacity) }
+    }
+
+    /// 获取 name 的编号, 首次出现时分配新的编号
+    pub fn intern(&mut self, name: &'a str) -> usize {
+        let next = self.names.len();
+        let id = *self.ids.entry(name).or_insert(next);
+        if id == next {
+            self.names.push(name);
+        }
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn resolve(&self, id: usize) -> &'a str {
+        self.names[id]
+    }
+
+    /// 按编号排列的全部名字
+    pub fn names(&self) -> &[&'a str] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+}
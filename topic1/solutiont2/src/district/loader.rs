@@ -0,0 +1,519 @@
+//! 城市图的读取
+//!
+//! # 支持的格式:
+//! - 邻接表 JSON: `{"1": {"成都": ["宜宾", "自贡"], ...}, "2": {...}}`, 同一层中重复的 key 会被合并
+//! - 边表 CSV: 每行 `batch,source,target` 或 `source,target` (此时全部归入批次 "1"),
+//!   可带表头, target 为空表示孤立的城市, `#` 开头的行为注释
+//! - DOT: 一个文件中可包含多个 `graph` / `digraph`, 每个图为一个批次, 边的方向被忽略
+//!
+//! # 参考文献:
+//! - https://github.com/serde-rs/json/issues/652
+//! - https://graphviz.org/doc/info/lang.html
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, Read, Write};
+use std::path::Path;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use crate::district::union_find::Merge;
+
+/// 一个批次的城市图, 以邻接表存储, 城市按首次出现的顺序排列
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Graph {
+    pub name: String,
+    pub adjacency: Vec<(String, Vec<String>)>,
+}
+
+impl Graph {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), adjacency: Vec::new() }
+    }
+
+    /// 添加一条无向边, to 为 None 时只登记城市本身
+    fn add(&mut self, index: &mut HashMap<String, usize>, from: &str, to: Option<&str>) {
+        let pos = *index.entry(from.to_string()).or_insert_with(|| {
+            self.adjacency.push((from.to_string(), Vec::new()));
+            self.adjacency.len() - 1
+        });
+        if let Some(to) = to {
+            self.adjacency[pos].1.push(to.to_string());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphFormat {
+    AdjacencyJson,
+    EdgeListCsv,
+    Dot,
+}
+
+impl GraphFormat {
+    /// 根据文件扩展名推断格式
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => Some(GraphFormat::AdjacencyJson),
+            "csv" => Some(GraphFormat::EdgeListCsv),
+            "dot" | "gv" => Some(GraphFormat::Dot),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),                             // 读取文件失败
+    UnknownFormat(String),                          // 无法从扩展名推断格式
+    Json(serde_json::Error),                        // JSON 格式错误
+    Csv(csv::Error),                                // CSV 格式错误
+    Syntax { line: usize, message: String },        // 行号从 1 开始
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "io error: {e}"),
+            LoadError::UnknownFormat(path) => write!(f, "cannot infer graph format of '{path}'"),
+            LoadError::Json(e) => write!(f, "invalid json: {e}"),
+            LoadError::Csv(e) => write!(f, "invalid csv: {e}"),
+            LoadError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError { }
+
+impl From<std::io::Error> for LoadError {
+    fn from(e: std::io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(e: serde_json::Error) -> Self {
+        LoadError::Json(e)
+    }
+}
+
+impl From<csv::Error> for LoadError {
+    fn from(e: csv::Error) -> Self {
+        LoadError::Csv(e)
+    }
+}
+
+/// 读取文件中的全部批次, 格式由扩展名决定
+pub fn load_path(path: impl AsRef<Path>) -> Result<Vec<Graph>, LoadError> {
+    let path = path.as_ref();
+    let format = GraphFormat::from_path(path)
+        .ok_or_else(|| LoadError::UnknownFormat(path.display().to_string()))?;
+    load_reader(BufReader::new(File::open(path)?), format)
+}
+
+/// 按指定格式读取全部批次
+pub fn load_reader(reader: impl Read, format: GraphFormat) -> Result<Vec<Graph>, LoadError> {
+    match format {
+        GraphFormat::AdjacencyJson => parse_json(reader),
+        GraphFormat::EdgeListCsv => parse_csv(reader),
+        GraphFormat::Dot => {
+            let mut text = String::new();
+            let mut reader = reader;
+            reader.read_to_string(&mut text)?;
+            parse_dot(&text)
+        }
+    }
+}
+
+/// 保持文档顺序的 map, 重复的 key 通过 Merge 合并
+///
+/// 城市的邻居列表直接拼接, 批次内容递归合并
+#[derive(Clone)]
+struct Ordered<V>(Vec<(String, V)>);
+
+impl<V: Merge + Clone> Merge for Ordered<V> {
+    fn merge(&mut self, other: &Self) {
+        for (key, value) in &other.0 {
+            match self.0.iter_mut().find(|(k, _)| k == key) {
+                Some((_, v)) => v.merge(value),
+                None => self.0.push((key.clone(), value.clone())),
+            }
+        }
+    }
+}
+
+impl<'de, V: Deserialize<'de> + Merge> Deserialize<'de> for Ordered<V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        struct OrderedVisitor<V>(std::marker::PhantomData<V>);
+        impl<'de, V: Deserialize<'de> + Merge> Visitor<'de> for OrderedVisitor<V> {
+            type Value = Ordered<V>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a JSON object, duplicate keys are merged")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>
+            {
+                // 在 visit 的时候根据自定义合并操作实现对应效果
+                let mut index: HashMap<String, usize> = HashMap::new();
+                let mut res: Vec<(String, V)> = Vec::new();
+                while let Some((key, value)) = map.next_entry::<String, V>()? {
+                    match index.get(&key) {
+                        Some(&pos) => res[pos].1.merge(&value),
+                        None => {
+                            index.insert(key.clone(), res.len());
+                            res.push((key, value));
+                        }
+                    }
+                }
+                Ok(Ordered(res))
+            }
+        }
+        deserializer.deserialize_map(OrderedVisitor(std::marker::PhantomData))
+    }
+}
+
+/// 以邻接表 JSON 的格式写出全部批次, 与 load_reader 互逆
+pub fn write_json(graphs: &[Graph], writer: impl Write) -> Result<(), LoadError> {
+    struct Batches<'a>(&'a [Graph]);
+    struct Adjacency<'a>(&'a [(String, Vec<String>)]);
+
+    impl Serialize for Batches<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(self.0.len()))?;
+            for graph in self.0 {
+                map.serialize_entry(&graph.name, &Adjacency(&graph.adjacency))?;
+            }
+            map.end()
+        }
+    }
+
+    impl Serialize for Adjacency<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(self.0.len()))?;
+            for (city, neighbors) in self.0 {
+                map.serialize_entry(city, neighbors)?;
+            }
+            map.end()
+        }
+    }
+
+    serde_json::to_writer(writer, &Batches(graphs))?;
+    Ok(())
+}
+
+fn parse_json(reader: impl Read) -> Result<Vec<Graph>, LoadError> {
+    let data: Ordered<Ordered<Vec<String>>> = serde_json::from_reader(reader)?;
+    Ok(data.0.into_iter()
+        .map(|(name, turn)| Graph { name, adjacency: turn.0 })
+        .collect())
+}
+
+fn parse_csv(reader: impl Read) -> Result<Vec<Graph>, LoadError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .comment(Some(b'#'))
+        .from_reader(reader);
+
+    let mut graphs: Vec<Graph> = Vec::new();
+    let mut index: HashMap<String, (usize, HashMap<String, usize>)> = HashMap::new();
+    for (row, record) in reader.records().enumerate() {
+        let record = record?;
+        let fields: Vec<&str> = record.iter().collect();
+        let (batch, from, to) = match fields.as_slice() {
+            [from] => ("1", *from, ""),
+            [from, to] => ("1", *from, *to),
+            [batch, from, to] => (*batch, *from, *to),
+            _ => return Err(LoadError::Syntax {
+                line: record.position().map_or(row + 1, |p| p.line() as usize),
+                message: format!("expected 2 or 3 columns, found {}", fields.len()),
+            }),
+        };
+        if row == 0 && is_header(&fields) {
+            continue;
+        }
+        if from.is_empty() {
+            return Err(LoadError::Syntax {
+                line: record.position().map_or(row + 1, |p| p.line() as usize),
+                message: "empty source city".to_string(),
+            });
+        }
+
+        let (pos, nodes) = index.entry(batch.to_string()).or_insert_with(|| {
+            graphs.push(Graph::new(batch));
+            (graphs.len() - 1, HashMap::new())
+        });
+        let graph = &mut graphs[*pos];
+        graph.add(nodes, from, (!to.is_empty()).then_some(to));
+        if !to.is_empty() {
+            graph.add(nodes, to, None);
+        }
+    }
+    Ok(graphs)
+}
+
+fn is_header(fields: &[&str]) -> bool {
+    const NAMES: [&str; 7] = ["batch", "source", "target", "from", "to", "city", "neighbor"];
+    fields.iter().all(|f| NAMES.contains(&f.to_ascii_lowercase().as_str()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String),
+    Edge,                   // -- 或 ->
+    Open,                   // {
+    Close,                  // }
+    AttrOpen,               // [
+    AttrClose,              // ]
+    Equal,
+    Separator,              // ; 或 ,
+    Colon,                  // 节点 ID 后的 :port
+}
+
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, LoadError> {
+    let mut res = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    let mut line_start = true;      // # 只在行首表示预处理指令
+    while let Some(c) = chars.next() {
+        let start_line = line;
+        let at_line_start = line_start;
+        line_start = c == '\n' || (line_start && c.is_whitespace());
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '#' if at_line_start => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            '/' if chars.next_if_eq(&'/').is_some() => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            '/' if chars.next_if_eq(&'*').is_some() => {
+                let mut prev = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if prev == '*' => break,
+                        Some(c) => {
+                            if c == '\n' { line += 1 }
+                            prev = c;
+                        }
+                        None => return Err(LoadError::Syntax { line: start_line, message: "unterminated comment".to_string() }),
+                    }
+                }
+            }
+            '-' if chars.next_if(|&c| c == '-' || c == '>').is_some() => res.push((Token::Edge, line)),
+            '{' => res.push((Token::Open, line)),
+            '}' => res.push((Token::Close, line)),
+            '[' => res.push((Token::AttrOpen, line)),
+            ']' => res.push((Token::AttrClose, line)),
+            '=' => res.push((Token::Equal, line)),
+            ';' | ',' => res.push((Token::Separator, line)),
+            ':' => res.push((Token::Colon, line)),
+            '"' => {
+                let mut id = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if chars.peek() == Some(&'"') => id.push(chars.next().unwrap()),
+                        // 行尾的反斜杠表示续行
+                        Some('\\') if chars.next_if_eq(&'\n').is_some() => line += 1,
+                        Some(c) => {
+                            if c == '\n' { line += 1 }
+                            id.push(c);
+                        }
+                        None => return Err(LoadError::Syntax { line: start_line, message: "unterminated string".to_string() }),
+                    }
+                }
+                res.push((Token::Id(id), start_line));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut id = c.to_string();
+                while let Some(c) = chars.next_if(|&c| c.is_alphanumeric() || c == '_' || c == '.') {
+                    id.push(c);
+                }
+                res.push((Token::Id(id), line));
+            }
+            c => return Err(LoadError::Syntax { line, message: format!("unexpected character '{c}'") }),
+        }
+    }
+    Ok(res)
+}
+
+/// 逐个 token 解析 DOT 文件
+struct DotParser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl DotParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens.get(self.pos).or(self.tokens.last()).map_or(1, |&(_, l)| l)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, LoadError> {
+        Err(LoadError::Syntax { line: self.line(), message: message.into() })
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(t, _)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn keyword(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Id(id)) if id.eq_ignore_ascii_case(word))
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), LoadError> {
+        match self.peek() {
+            Some(t) if *t == token => {
+                self.pos += 1;
+                Ok(())
+            }
+            other => self.error(format!("expected {token:?}, found {other:?}")),
+        }
+    }
+
+    /// [strict] (graph | digraph) [ID] { stmt_list }
+    fn graph(&mut self, default_name: String) -> Result<Graph, LoadError> {
+        if self.keyword("strict") {
+            self.pos += 1;
+        }
+        if !(self.keyword("graph") || self.keyword("digraph")) {
+            return self.error("expected 'graph' or 'digraph'");
+        }
+        self.pos += 1;
+        let name = match self.peek() {
+            Some(Token::Id(_)) => match self.next() {
+                Some(Token::Id(id)) => id,
+                _ => unreachable!(),
+            },
+            _ => default_name,
+        };
+        let mut graph = Graph::new(name);
+        let mut nodes = HashMap::new();
+        self.expect(Token::Open)?;
+        self.statements(&mut graph, &mut nodes)?;
+        Ok(graph)
+    }
+
+    /// 解析到与之匹配的 } 为止, 子图中的语句直接并入当前图
+    fn statements(&mut self, graph: &mut Graph, nodes: &mut HashMap<String, usize>) -> Result<(), LoadError> {
+        loop {
+            match self.peek() {
+                None => return self.error("unexpected end of file, missing '}'"),
+                Some(Token::Close) => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(Token::Separator) => self.pos += 1,
+                Some(Token::Open) => {
+                    self.pos += 1;
+                    self.statements(graph, nodes)?;
+                }
+                Some(Token::Id(_)) if self.keyword("subgraph") => {
+                    self.pos += 1;
+                    if let Some(Token::Id(_)) = self.peek() {
+                        self.pos += 1;
+                    }
+                    self.expect(Token::Open)?;
+                    self.statements(graph, nodes)?;
+                }
+                Some(Token::Id(_)) if self.keyword("graph") || self.keyword("node") || self.keyword("edge") => {
+                    self.pos += 1;
+                    self.attributes()?;
+                }
+                Some(Token::Id(_)) => self.node_or_edge(graph, nodes)?,
+                Some(other) => return self.error(format!("unexpected {other:?}")),
+            }
+        }
+    }
+
+    /// ID = ID | node_id [attr_list] | node_id (-- node_id)+ [attr_list]
+    fn node_or_edge(&mut self, graph: &mut Graph, nodes: &mut HashMap<String, usize>) -> Result<(), LoadError> {
+        let mut chain = vec![self.node_id()?];
+        if self.peek() == Some(&Token::Equal) {
+            self.pos += 1;
+            self.node_id()?;
+            return Ok(());
+        }
+        while self.peek() == Some(&Token::Edge) {
+            self.pos += 1;
+            if self.keyword("subgraph") || self.peek() == Some(&Token::Open) {
+                return self.error("subgraph as edge endpoint is not supported");
+            }
+            chain.push(self.node_id()?);
+        }
+        self.attributes()?;
+
+        if let [single] = chain.as_slice() {
+            graph.add(nodes, single, None);
+        }
+        for pair in chain.windows(2) {
+            graph.add(nodes, &pair[0], Some(&pair[1]));
+            graph.add(nodes, &pair[1], None);
+        }
+        Ok(())
+    }
+
+    /// ID [: port [: compass]], 端口部分被忽略; 带引号的 ID 中的 : 属于名字本身
+    fn node_id(&mut self) -> Result<String, LoadError> {
+        let id = match self.next() {
+            Some(Token::Id(id)) => id,
+            other => {
+                self.pos -= 1;
+                return self.error(format!("expected node id, found {other:?}"));
+            }
+        };
+        while self.peek() == Some(&Token::Colon) {
+            self.pos += 1;
+            match self.next() {
+                Some(Token::Id(_)) => {}
+                other => {
+                    self.pos -= 1;
+                    return self.error(format!("expected port, found {other:?}"));
+                }
+            }
+        }
+        Ok(id)
+    }
+
+    /// 跳过任意数量的 [a = b, ...]
+    fn attributes(&mut self) -> Result<(), LoadError> {
+        while self.peek() == Some(&Token::AttrOpen) {
+            self.pos += 1;
+            loop {
+                match self.next() {
+                    Some(Token::AttrClose) => break,
+                    Some(Token::Id(_) | Token::Equal | Token::Separator | Token::Colon) => {}
+                    _ => {
+                        self.pos -= 1;
+                        return self.error("unterminated attribute list");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_dot(text: &str) -> Result<Vec<Graph>, LoadError> {
+    let mut parser = DotParser { tokens: tokenize(text)?, pos: 0 };
+    let mut graphs = Vec::new();
+    while parser.peek().is_some() {
+        graphs.push(parser.graph((graphs.len() + 1).to_string())?);
+    }
+    Ok(graphs)
+}
@@ -1,47 +1,194 @@
 //! 并查集
-//! 
+//!
 //! # 实现原理:
-//! 存在两个矩阵 data = Vec<data>; self.fa = Vec<usize>; 
-//! - data.len() == self.fa.len(); 二者一一对应
-//! - self.fa.get(idx) 代表着对应 data.get(idx) 所从属的集合类别
-//! - 集合类别以另一元素的 idx 作为标记 (采用路径压缩, 因此多个归属于统一集合的元素会具有同一数值)
-//!     - 如 self.fa[3] = 7 -> data 中第四个元素与第七个元素从属于同一个集合
+//! - fa[x] 为 x 的父节点, 根节点满足 fa[root] == root, 同一棵树中的元素属于同一个集合
+//! - size[root] 与 meta[root] 记录集合的大小与元数据, 只在根节点上有意义
+//! - find 沿 fa 走到根, DSU 在返回前把路径上的节点直接挂到根上 (路径压缩)
+//! - union 把较小集合的根挂到较大集合的根下, 同时合并元数据并把集合个数减一
+//!
+//! # 主要特点:
+//! - 按集合大小合并, 小集合挂到大集合的根上, 树高为 O(log n)
+//! - 迭代实现的路径压缩, 长链也不会栈溢出
+//! - 集合个数在合并时维护, 查询为 O(1)
+//! - 每个集合可以携带自定义的元数据, 合并集合时一并合并
+//! - RollbackDSU 不做路径压缩, 可以按相反顺序撤销合并, 用于离线的动态连通性查询
+
+/// 集合携带的元数据, 两个集合合并时 other 被并入 self
+pub trait Merge {
+    fn merge(&mut self, other: &Self);
+}
+
+impl Merge for () {
+    fn merge(&mut self, _other: &Self) { }
+}
+
+impl<T: Clone> Merge for Vec<T> {
+    fn merge(&mut self, other: &Self) {
+        self.extend_from_slice(other);
+    }
+}
+
 #[derive(Debug, Clone)]
-pub struct DSU {
+pub struct DSU<M = ()> {
     /// 一个代表一系列元素的集合指示符的 vector
     fa: Vec<usize>,
+    /// size[root]: 以 root 为根的集合大小, 非根节点上的值无意义
+    size: Vec<usize>,
+    /// meta[root]: 集合的元数据, 非根节点上的值无意义
+    meta: Vec<M>,
+    sets: usize,
 }
 
 impl DSU {
     pub fn with_capacity(size: usize) -> Self {
-       DSU { fa: (0..size).collect() } 
+        DSU::with_metadata(vec![(); size])
+    }
+}
+
+impl<M: Merge> DSU<M> {
+    /// 以每个元素各自的元数据初始化, 元素个数为 meta.len()
+    pub fn with_metadata(meta: Vec<M>) -> Self {
+        let len = meta.len();
+        DSU { fa: (0..len).collect(), size: vec![1; len], meta, sets: len }
     }
 
-    /// 
-    fn find(&mut self, x: usize) -> usize {
-        // 当前节点是否是 孤立的叶子节点 
-        if self.fa[x] != x {
-            self.fa[x] = self.find(self.fa[x]);
+    /// 查找 x 所在集合的根节点
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.fa[root] != root {
+            root = self.fa[root];
+        }
+        // 第二遍将路径上的节点直接指向根
+        let mut curr = x;
+        while self.fa[curr] != root {
+            let next = self.fa[curr];
+            self.fa[curr] = root;
+            curr = next;
         }
-        self.fa[x]
+        root
     }
 
-    // 如果二者不归属于同一个集合, 则将 x 的集合指示符设定为 y 的集合指示符
-    pub fn union(&mut self, x: usize, y: usize) {
-        let root_x = self.find(x);
-        let root_y = self.find(y);
-        if root_x != root_y {
-            self.fa[root_x] = root_y;
+    /// 合并 x 与 y 所在的集合, 二者原本已在同一集合时返回 false
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let mut root_x = self.find(x);
+        let mut root_y = self.find(y);
+        if root_x == root_y {
+            return false;
+        }
+        if self.size[root_x] > self.size[root_y] {
+            std::mem::swap(&mut root_x, &mut root_y);
         }
+        // 小集合 root_x 挂到大集合 root_y 下
+        self.fa[root_x] = root_y;
+        self.size[root_y] += self.size[root_x];
+        let (child, root) = get_pair(&mut self.meta, root_x, root_y);
+        root.merge(child);
+        self.sets -= 1;
+        true
     }
-    
+
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// x 所在集合的元素个数
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// x 所在集合的元数据
+    pub fn metadata(&mut self, x: usize) -> &M {
+        let root = self.find(x);
+        &self.meta[root]
+    }
+
     pub fn count_sets(&self) -> usize {
-        let mut cnt: usize = 0;
-        for idx in 0..self.fa.len() {
-            if self.fa.get(idx) == Some(&idx) {
-                cnt += 1;
+        self.sets
+    }
+}
+
+/// 同时获取 a, b 两个位置的引用, a != b
+fn get_pair<M>(v: &mut [M], a: usize, b: usize) -> (&M, &mut M) {
+    if a < b {
+        let (left, right) = v.split_at_mut(b);
+        (&left[a], &mut right[0])
+    } else {
+        let (left, right) = v.split_at_mut(a);
+        (&right[0], &mut left[b])
+    }
+}
+
+/// 可撤销的并查集
+///
+/// 只按大小合并而不做路径压缩, find 为 O(log n);
+/// 每次 union 都会记录一条历史, 即使两者已在同一集合, 以便与操作一一对应地撤销
+#[derive(Debug, Clone)]
+pub struct RollbackDSU {
+    fa: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+    /// 每次 union 被挂到另一个根下的节点, None 表示该次 union 没有发生合并
+    history: Vec<Option<usize>>,
+}
+
+impl RollbackDSU {
+    pub fn with_capacity(size: usize) -> Self {
+        RollbackDSU { fa: (0..size).collect(), size: vec![1; size], sets: size, history: Vec::new() }
+    }
+
+    pub fn find(&self, mut x: usize) -> usize {
+        while self.fa[x] != x {
+            x = self.fa[x];
+        }
+        x
+    }
+
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let mut root_x = self.find(x);
+        let mut root_y = self.find(y);
+        if root_x == root_y {
+            self.history.push(None);
+            return false;
+        }
+        if self.size[root_x] > self.size[root_y] {
+            std::mem::swap(&mut root_x, &mut root_y);
+        }
+        self.fa[root_x] = root_y;
+        self.size[root_y] += self.size[root_x];
+        self.sets -= 1;
+        self.history.push(Some(root_x));
+        true
+    }
+
+    pub fn count_sets(&self) -> usize {
+        self.sets
+    }
+
+    /// 当前的历史长度, 可传给 rollback 回到此时的状态
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// 撤销最近一次 union, 没有历史时返回 false
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(Some(child)) => {
+                let root = self.fa[child];
+                self.size[root] -= self.size[child];
+                self.fa[child] = child;
+                self.sets += 1;
+                true
             }
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    /// 撤销 snapshot 之后的全部 union
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            self.undo();
         }
-        cnt
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,167 @@
 mod district;
 
 fn main() {
-    let provinces = district::count_provinces();
-    println!("provinces: {provinces}");
+    // 不带参数时统计 district.json, 否则读取给定的 JSON / CSV / DOT 文件
+    // 路径为 - 时从标准输入读取, 需要额外指定格式: solutiont2 - json|csv|dot
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(path) = args.first() else {
+        let provinces = district::count_provinces();
+        println!("provinces: {provinces}");
+        return;
+    };
+    if path == "--changes" {
+        replay_changes(&args[1..]);
+        return;
+    }
+    if path == "--analyze" || path == "--path" {
+        analyze(path == "--path", &args[1..]);
+        return;
+    }
+    if path == "--generate" || path == "--bench" {
+        random_input(path == "--bench", &args[1..]);
+        return;
+    }
+
+    let result = if path == "-" {
+        let format = match args.get(1).map(String::as_str) {
+            Some("json") => district::GraphFormat::AdjacencyJson,
+            Some("csv") => district::GraphFormat::EdgeListCsv,
+            Some("dot") => district::GraphFormat::Dot,
+            _ => {
+                eprintln!("usage: solutiont2 - json|csv|dot");
+                std::process::exit(1);
+            }
+        };
+        district::provinces_from_reader(std::io::stdin().lock(), format)
+    } else {
+        district::provinces_from_path(path)
+    };
+
+    match result {
+        Ok(batches) => {
+            for batch in batches {
+                println!("[{}] provinces: {}", batch.name, batch.count);
+                for members in batch.members {
+                    println!("    {}", members.join(", "));
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("{path}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// solutiont2 --changes <log> [graph]: 以 graph 的第一个批次 (缺省为 district.json) 为初始状态回放变更日志
+fn replay_changes(args: &[String]) {
+    let manifest_path = env!("CARGO_MANIFEST_DIR");
+    let default_graph = format!("{manifest_path}/district.json");
+    let (Some(log), graph) = (args.first(), args.get(1).unwrap_or(&default_graph)) else {
+        eprintln!("usage: solutiont2 --changes <log> [graph]");
+        std::process::exit(1);
+    };
+
+    let initial = match district::load_path(graph) {
+        Ok(batches) => batches.into_iter().next().unwrap_or_default(),
+        Err(e) => {
+            eprintln!("{graph}: {e}");
+            std::process::exit(1);
+        }
+    };
+    let counts = district::load_change_log(log).and_then(|changes| {
+        let counts = district::replay(&initial, &changes)?;
+        Ok(changes.into_iter().zip(counts.into_iter().skip(1)).collect::<Vec<_>>())
+    });
+    match counts {
+        Ok(steps) => {
+            for (entry, count) in steps {
+                println!("line {:>3}: {:?} -> provinces: {count}", entry.line, entry.change);
+            }
+        }
+        Err(e) => {
+            eprintln!("{log}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// solutiont2 --generate <cities> <roads> [seed]: 以 JSON 格式向标准输出写出随机城市图
+/// solutiont2 --bench <cities> <roads> [seed]: 生成随机城市图并统计连通块所用的时间
+fn random_input(bench: bool, args: &[String]) {
+    let parse = |idx: usize, default: Option<u64>| -> u64 {
+        match args.get(idx).map(|s| s.parse::<u64>()) {
+            Some(Ok(value)) => value,
+            None if default.is_some() => default.unwrap(),
+            _ => {
+                eprintln!("usage: solutiont2 --generate|--bench <cities> <roads> [seed]");
+                std::process::exit(1);
+            }
+        }
+    };
+    let (cities, roads, seed) = (parse(0, None) as usize, parse(1, None) as usize, parse(2, Some(0)));
+
+    let start = std::time::Instant::now();
+    let graph = district::random_graph(cities, roads, seed);
+    if !bench {
+        let stdout = std::io::stdout().lock();
+        if let Err(e) = district::write_json(&[graph], std::io::BufWriter::new(stdout)) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+    let generated = start.elapsed();
+    let start = std::time::Instant::now();
+    let provinces = district::count_connected_components(&graph);
+    println!(
+        "{cities} cities, {roads} roads: {} provinces, generated in {generated:?}, counted in {:?}",
+        provinces.count,
+        start.elapsed()
+    );
+}
+
+/// solutiont2 --analyze [graph]: 以 JSON 输出每个批次的连通块, 最大连通块, 桥与割点
+/// solutiont2 --path <from> <to> [graph]: 以 JSON 输出每个批次中两个城市之间的最短路径
+fn analyze(path_only: bool, args: &[String]) {
+    let (cities, rest) = if path_only {
+        match args {
+            [from, to, rest @ ..] => (Some((from, to)), rest),
+            _ => {
+                eprintln!("usage: solutiont2 --path <from> <to> [graph]");
+                std::process::exit(1);
+            }
+        }
+    } else {
+        (None, args)
+    };
+    let manifest_path = env!("CARGO_MANIFEST_DIR");
+    let default_graph = format!("{manifest_path}/district.json");
+    let graph = rest.first().unwrap_or(&default_graph);
+
+    let batches = match district::load_path(graph) {
+        Ok(batches) => batches,
+        Err(e) => {
+            eprintln!("{graph}: {e}");
+            std::process::exit(1);
+        }
+    };
+    let output = match cities {
+        Some((from, to)) => serde_json::to_string_pretty(&batches.iter()
+            .map(|batch| {
+                let path = district::CityGraph::new(batch).shortest_path(from, to);
+                serde_json::json!({
+                    "name": batch.name,
+                    "from": from,
+                    "to": to,
+                    "hops": path.as_ref().map(|p| p.len() - 1),
+                    "path": path,
+                })
+            })
+            .collect::<Vec<_>>()),
+        None => serde_json::to_string_pretty(&batches.iter()
+            .map(|batch| district::CityGraph::new(batch).analyze(&batch.name))
+            .collect::<Vec<_>>()),
+    };
+    println!("{}", output.unwrap());
 }
@@ -1,7 +1,11 @@
 mod district;
 #[cfg(test)]
 mod tests {
-    use super::district::count_provinces;
+    use super::district::{count_provinces, provinces_from_path, provinces_from_reader, GraphFormat, LoadError};
+    use super::district::{load_change_log, load_path, replay, Graph};
+    use super::district::{count_connected_components, random_graph, write_json, CityGraph};
+    use super::district::dynamic::{parse_change_log, ChangeLogError};
+    use super::district::union_find::{Merge, RollbackDSU, DSU};
     use std::time::{Instant, Duration};
 
     // 定义测试用例和预期结果
@@ -23,4 +27,169 @@ mod tests {
         println!("Total score: {:.2}", total_score);
         assert_eq!(100.00, total_score);
     }
+
+    #[test]
+    fn test_graph_formats() {
+        let json = r#"{"a": {"x": ["y"], "z": [], "x": ["w"]}, "b": {"p": ["q"]}}"#;
+        let res = provinces_from_reader(json.as_bytes(), GraphFormat::AdjacencyJson).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!((res[0].name.as_str(), res[0].count), ("a", 2));
+        assert_eq!(res[0].members, vec![vec!["x", "y", "w"], vec!["z"]]);
+
+        let csv = "batch,source,target\n1,成都,宜宾\n1,\"台北\",\n# comment\n2,a,b\n1,宜宾,绵阳\n";
+        let res = provinces_from_reader(csv.as_bytes(), GraphFormat::EdgeListCsv).unwrap();
+        assert_eq!(res.iter().map(|p| p.count).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(res[0].members, vec![vec!["成都", "宜宾", "绵阳"], vec!["台北"]]);
+
+        let dot = r#"
+            // 两个批次
+            graph first { a -- b -- c; d [label="孤立"]; subgraph s { e -> f } }
+            strict digraph { "上海" -> 浦东:n; node [shape=box]; }
+        "#;
+        let res = provinces_from_reader(dot.as_bytes(), GraphFormat::Dot).unwrap();
+        assert_eq!((res[0].name.as_str(), res[0].count), ("first", 3));
+        assert_eq!((res[1].name.as_str(), res[1].count), ("2", 1));
+        assert_eq!(res[1].members, vec![vec!["上海", "浦东"]]);
+
+        // 带引号的 ID 中的 : 不是端口
+        let dot = r#"graph { "A:B" -- "C":e; c:p:sw -- "A:B":n }"#;
+        let res = provinces_from_reader(dot.as_bytes(), GraphFormat::Dot).unwrap();
+        assert_eq!(res[0].members, vec![vec!["A:B", "C", "c"]]);
+
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("district.json");
+        let counts: Vec<usize> = provinces_from_path(path).unwrap().iter().map(|p| p.count).collect();
+        assert_eq!(counts, vec![3, 3, 2, 2, 1]);
+
+        let err = provinces_from_reader("graph { a -- }".as_bytes(), GraphFormat::Dot).unwrap_err();
+        assert!(matches!(err, LoadError::Syntax { line: 1, .. }));
+        let err = provinces_from_reader("a,b,c,d".as_bytes(), GraphFormat::EdgeListCsv).unwrap_err();
+        assert!(matches!(err, LoadError::Syntax { .. }));
+    }
+
+    #[test]
+    fn test_union_find() {
+        // 超长链: 递归实现会栈溢出
+        let n = 1_000_000;
+        let mut dsu = DSU::with_capacity(n);
+        for i in 1..n {
+            assert!(dsu.union(i - 1, i));
+        }
+        assert!(!dsu.union(0, n - 1));
+        assert_eq!((dsu.count_sets(), dsu.size(0)), (1, n));
+
+        // 元数据: 集合中的人口总数与最大城市
+        #[derive(Debug, PartialEq)]
+        struct City { population: u32, largest: u32 }
+        impl Merge for City {
+            fn merge(&mut self, other: &Self) {
+                self.population += other.population;
+                self.largest = self.largest.max(other.largest);
+            }
+        }
+        let meta = [5, 3, 8, 1].map(|p| City { population: p, largest: p });
+        let mut dsu = DSU::with_metadata(meta.into());
+        dsu.union(0, 1);
+        dsu.union(3, 2);
+        assert_eq!(dsu.count_sets(), 2);
+        assert_eq!(dsu.metadata(1), &City { population: 8, largest: 5 });
+        dsu.union(1, 3);
+        assert!(dsu.same(0, 2));
+        assert_eq!(dsu.metadata(2), &City { population: 17, largest: 8 });
+
+        let mut dsu = RollbackDSU::with_capacity(5);
+        dsu.union(0, 1);
+        let snapshot = dsu.snapshot();
+        dsu.union(1, 2);
+        dsu.union(0, 2);
+        dsu.union(3, 4);
+        assert_eq!(dsu.count_sets(), 2);
+        assert_eq!(dsu.find(0), dsu.find(2));
+        assert!(dsu.undo());
+        assert_eq!(dsu.count_sets(), 3);
+        dsu.rollback(snapshot);
+        assert_eq!(dsu.count_sets(), 4);
+        assert_eq!(dsu.find(0), dsu.find(1));
+        assert_ne!(dsu.find(1), dsu.find(2));
+        dsu.rollback(0);
+        assert!(!dsu.undo());
+        assert_eq!(dsu.count_sets(), 5);
+    }
+
+    #[test]
+    fn test_dynamic_connectivity() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let initial = load_path(dir.join("district.json")).unwrap().remove(0);
+        let changes = load_change_log(dir.join("district_changes.log")).unwrap();
+        assert_eq!(replay(&initial, &changes).unwrap(), vec![3, 2, 2, 3, 3, 3, 4, 4, 5]);
+
+        // 同一条道路反复建立与拆除
+        let log = "+ a b\n+ b c\n- b a\n+ a b\n- c b\n- a b\n";
+        let changes = parse_change_log(log.as_bytes()).unwrap();
+        assert_eq!(replay(&Graph::default(), &changes).unwrap(), vec![0, 1, 1, 2, 1, 2, 3]);
+
+        let err = replay(&Graph::default(), &parse_change_log("+ a b\n\n- a c".as_bytes()).unwrap()).unwrap_err();
+        assert!(matches!(err, ChangeLogError::MissingRoad { line: 3, .. }));
+        let err = replay(&Graph::default(), &parse_change_log("+ a b\n+ b a".as_bytes()).unwrap()).unwrap_err();
+        assert!(matches!(err, ChangeLogError::DuplicateRoad { line: 2, .. }));
+        assert!(matches!(parse_change_log("* a b".as_bytes()), Err(ChangeLogError::Syntax { line: 1, .. })));
+        assert!(matches!(parse_change_log("- a".as_bytes()), Err(ChangeLogError::Syntax { line: 1, .. })));
+        let err = parse_change_log("+ a b c".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "line 1: '+' expects 1 or 2 cities, found 3");
+    }
+
+    #[test]
+    fn test_large_graph() {
+        let graph = random_graph(200_000, 150_000, 7);
+        let provinces = count_connected_components(&graph);
+        // 每条道路最多减少一个连通块
+        assert!(provinces.count >= 200_000 - 150_000);
+        assert_eq!(provinces.count, provinces.members.len());
+        assert_eq!(provinces.members.iter().map(Vec::len).sum::<usize>(), 200_000);
+
+        // 写出后重新读取, 结果不变
+        let small = random_graph(500, 300, 1);
+        let mut buffer = Vec::new();
+        write_json(std::slice::from_ref(&small), &mut buffer).unwrap();
+        let res = provinces_from_reader(buffer.as_slice(), GraphFormat::AdjacencyJson).unwrap();
+        assert_eq!(res, vec![count_connected_components(&small)]);
+        assert_eq!(count_connected_components(&random_graph(10, 0, 0)).count, 10);
+    }
+
+    #[test]
+    fn test_graph_analysis() {
+        // 两个三角形由 c - d 相连, d - e - f 为一条链, 另有孤立的 g
+        let dot = "graph { a -- b -- c -- a; c -- d; d -- e -- f; d -- h -- i -- d; b -- a; g }";
+        let graph = provinces_from_reader(dot.as_bytes(), GraphFormat::Dot).unwrap();
+        assert_eq!(graph[0].count, 2);
+
+        let batch = &super::district::load_reader(dot.as_bytes(), GraphFormat::Dot).unwrap()[0];
+        let city_graph = CityGraph::new(batch);
+        assert_eq!((city_graph.city_count(), city_graph.road_count()), (9, 9));
+        assert_eq!(city_graph.bridges(), vec![("c", "d"), ("d", "e"), ("e", "f")]);
+        assert_eq!(city_graph.articulation_points(), vec!["c", "d", "e"]);
+        assert_eq!(city_graph.largest_component(), vec!["a", "b", "c", "d", "e", "h", "f", "i"]);
+        assert_eq!(city_graph.shortest_path("a", "f"), Some(vec!["a", "c", "d", "e", "f"]));
+        assert_eq!(city_graph.shortest_path("g", "g"), Some(vec!["g"]));
+        assert_eq!(city_graph.shortest_path("a", "g"), None);
+        assert_eq!(city_graph.shortest_path("a", "不存在"), None);
+
+        let analysis = city_graph.analyze(&batch.name);
+        assert_eq!(analysis.provinces, 2);
+        assert_eq!(analysis.components[1], vec!["g"]);
+        let json = serde_json::to_value(&analysis).unwrap();
+        assert_eq!(json["bridges"][0], serde_json::json!(["c", "d"]));
+
+        // district.json 第二批中的武汉连接着宜昌与黄冈, 荆州
+        let batch = &load_path(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("district.json")).unwrap()[1];
+        let city_graph = CityGraph::new(batch);
+        assert!(city_graph.articulation_points().contains(&"武汉"));
+        assert_eq!(city_graph.shortest_path("襄阳", "黄冈"), Some(vec!["襄阳", "宜昌", "武汉", "黄冈"]));
+
+        // 空图与长链 (不会栈溢出)
+        let empty = random_graph(0, 0, 0);
+        assert!(CityGraph::new(&empty).bridges().is_empty());
+        let mut line = Graph::new("line");
+        line.adjacency = (0..200_000).map(|i| (i.to_string(), vec![(i + 1).to_string()])).collect();
+        assert_eq!(CityGraph::new(&line).bridges().len(), 200_000);
+    }
 }
@@ -0,0 +1,147 @@
+//! 工作日与节假日
+//!
+//! # 主要特点:
+//! - 节假日与调休上班日从数据文件读取, 其余日期按星期判断: 周一至周五上班, 周六周日休息
+//! - 星期由蔡勒公式 (zellers_kongruenz) 计算
+//! - 区间内的工作日数先按整周计数, 再用有序表中落在区间内的节假日与调休日修正, 与区间长度无关
+//!
+//! # 数据文件格式:
+//! 每行一条, `#` 之后为注释, 日期区间 `a..b` 包含两端
+//! ```text
+//! 休 2025-1-28..2025-2-4 春节      # 放假, 也可以写作 holiday
+//! 班 2025-1-26 春节调休            # 调休上班, 也可以写作 workday
+//! ```
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use super::date::Weekday;
+use super::{zellers_kongruenz, Date, DateError};
+
+#[derive(Debug)]
+pub enum CalendarError {
+    Io(std::io::Error),                             // 读取文件失败
+    Syntax { line: usize, message: String },        // 无法解析的行
+    Date { line: usize, error: DateError },         // 日期不合法
+    Conflict { line: usize, date: Date },           // 同一天既放假又上班
+}
+
+impl Display for CalendarError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalendarError::Io(e) => write!(f, "io error: {e}"),
+            CalendarError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            CalendarError::Date { line, error } => write!(f, "line {line}: {error}"),
+            CalendarError::Conflict { line, date } => write!(f, "line {line}: {date} is both a holiday and a working day"),
+        }
+    }
+}
+
+impl std::error::Error for CalendarError { }
+
+impl From<std::io::Error> for CalendarError {
+    fn from(e: std::io::Error) -> Self {
+        CalendarError::Io(e)
+    }
+}
+
+/// 节假日日历, 值为节日名称或调休说明
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HolidayCalendar {
+    holidays: BTreeMap<Date, String>,
+    workdays: BTreeMap<Date, String>,
+}
+
+impl HolidayCalendar {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CalendarError> {
+        Self::parse(File::open(path)?)
+    }
+
+    pub fn parse(reader: impl Read) -> Result<Self, CalendarError> {
+        let mut calendar = HolidayCalendar::default();
+        for (idx, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let line_no = idx + 1;
+            let content = line.split('#').next().unwrap_or_default();
+            let mut words = content.split_whitespace();
+            let Some(kind) = words.next() else { continue };
+            let syntax = |message: String| CalendarError::Syntax { line: line_no, message };
+            let range = words.next().ok_or_else(|| syntax(format!("'{kind}' expects a date or a date range")))?;
+            let name = words.collect::<Vec<&str>>().join(" ");
+
+            let date = |s: &str| s.parse::<Date>().map_err(|error| CalendarError::Date { line: line_no, error });
+            let (start, end) = match range.split_once("..") {
+                Some((start, end)) => (date(start)?, date(end)?),
+                None => (date(range)?, date(range)?),
+            };
+            if end < start {
+                return Err(syntax(format!("empty date range {start}..{end}")));
+            }
+
+            let (target, other) = match kind {
+                "休" | "holiday" => (&mut calendar.holidays, &calendar.workdays),
+                "班" | "workday" => (&mut calendar.workdays, &calendar.holidays),
+                _ => return Err(syntax(format!("unknown kind '{kind}', expects 休 or 班"))),
+            };
+            let mut curr = start;
+            while curr <= end {
+                if other.contains_key(&curr) {
+                    return Err(CalendarError::Conflict { line: line_no, date: curr });
+                }
+                target.insert(curr, name.clone());
+                curr = curr.add_days(1).map_err(|error| CalendarError::Date { line: line_no, error })?;
+            }
+        }
+        Ok(calendar)
+    }
+
+    /// date 为法定节假日时返回节日名称
+    pub fn holiday(&self, date: Date) -> Option<&str> {
+        self.holidays.get(&date).map(String::as_str)
+    }
+
+    pub fn is_working_day(&self, date: Date) -> bool {
+        if self.holidays.contains_key(&date) {
+            false
+        } else {
+            self.workdays.contains_key(&date) || !weekday(date).is_weekend()
+        }
+    }
+
+    /// date 之后 (n > 0) 或之前 (n < 0) 的第 |n| 个工作日, 不计 date 本身; n 为 0 时返回 date
+    pub fn add_working_days(&self, date: Date, n: i64) -> Result<Date, DateError> {
+        let step = n.signum();
+        let mut curr = date;
+        let mut remain = n.unsigned_abs();
+        while remain > 0 {
+            curr = curr.add_days(step)?;
+            if self.is_working_day(curr) {
+                remain -= 1;
+            }
+        }
+        Ok(curr)
+    }
+
+    /// [start, end) 中的工作日数, end 早于 start 时为 [end, start) 中工作日数的相反数
+    pub fn working_days_between(&self, start: Date, end: Date) -> i64 {
+        if end < start {
+            return -self.working_days_between(end, start);
+        }
+        let days = end - start;
+        // 整周各有 5 个周一至周五, 余下不足一周的部分逐天判断
+        let mut count = days / 7 * 5;
+        let first = weekday(start).number_from_monday() as i64;
+        count += (0..days % 7).filter(|offset| (first - 1 + offset) % 7 < 5).count() as i64;
+
+        let holidays = self.holidays.range(start..end).filter(|(&date, _)| !weekday(date).is_weekend()).count();
+        let workdays = self.workdays.range(start..end).filter(|(&date, _)| weekday(date).is_weekend()).count();
+        count - holidays as i64 + workdays as i64
+    }
+}
+
+fn weekday(date: Date) -> Weekday {
+    // 日期已经过校验, 蔡勒公式不会失败
+    let n = zellers_kongruenz(date.year(), date.month(), date.day()).unwrap();
+    Weekday::from_sunday(n).unwrap()
+}
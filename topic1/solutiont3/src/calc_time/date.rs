@@ -0,0 +1,274 @@
+//! 经过校验的公历日期
+//!
+//! # 主要特点:
+//! - 预推格里高利历 (proleptic Gregorian), 天文纪年: 0 年即公元前 1 年, -1 年即公元前 2 年
+//! - 日序号 (day number) 为距 1970-01-01 的天数, 日期的加减与比较都在日序号上进行
+//! - 与儒略历等其他历法之间通过儒略日数 (JDN) 换算, 见 julian 模块
+//! - ISO 8601 周历: 一年的第一周是包含该年第一个星期四的那一周, 年初年末的几天可能属于相邻的周年
+//!
+//! # 参考文献:
+//! - https://howardhinnant.github.io/date_algorithms.html
+//! - https://en.wikipedia.org/wiki/ISO_week_date
+use std::fmt::{Display, Formatter};
+use std::ops::Sub;
+use std::str::FromStr;
+
+/// 支持的年份范围, 保证日序号的运算不会溢出
+pub const MIN_YEAR: i32 = -999_999;
+pub const MAX_YEAR: i32 = 999_999;
+
+/// 1970-01-01 的儒略日数
+pub const UNIX_EPOCH_JDN: i64 = 2_440_588;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateError {
+    Format(String),                                 // 不符合 YYYY-M-D 的格式
+    InvalidNumber(String),                          // 年月日中某一部分不是合法的数字
+    YearOutOfRange(i64),                            // 年份超出 MIN_YEAR..=MAX_YEAR
+    InvalidMonth(u32),                              // 月份不在 1..=12
+    InvalidDay { year: i32, month: u32, day: u32 }, // 该月没有这一天
+    Overflow,                                       // 日期运算的结果超出支持范围
+}
+
+impl Display for DateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateError::Format(s) => write!(f, "'{s}' is not in YYYY-M-D format"),
+            DateError::InvalidNumber(s) => write!(f, "'{s}' is not a valid number"),
+            DateError::YearOutOfRange(year) => write!(f, "year {year} is out of range {MIN_YEAR}..={MAX_YEAR}"),
+            DateError::InvalidMonth(month) => write!(f, "month {month} is out of range 1..=12"),
+            DateError::InvalidDay { year, month, day } => write!(f, "{year}-{month} has no day {day}"),
+            DateError::Overflow => write!(f, "date arithmetic overflow"),
+        }
+    }
+}
+
+impl std::error::Error for DateError { }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Monday = 1,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday,
+        Weekday::Friday, Weekday::Saturday, Weekday::Sunday,
+    ];
+
+    /// 周一为 1, 周日为 7 (ISO 8601)
+    pub fn number_from_monday(self) -> u32 {
+        self as u32
+    }
+
+    /// n 为 1..=7, 周一为 1
+    pub fn from_monday(n: u32) -> Option<Self> {
+        Self::ALL.get((n as usize).checked_sub(1)?).copied()
+    }
+
+    /// n 为 0..=6, 周日为 0
+    pub fn from_sunday(n: u32) -> Option<Self> {
+        match n {
+            0 => Some(Weekday::Sunday),
+            _ => Self::from_monday(n).filter(|_| n < 7),
+        }
+    }
+
+    pub fn is_weekend(self) -> bool {
+        matches!(self, Weekday::Saturday | Weekday::Sunday)
+    }
+}
+
+/// 判断是否为闰年
+/// 参照公历规范实现： 闰年是指能被4整除的年份，但如果能被100整除而不能被400整除的年份就不是闰年
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+}
+
+/// 某年某月的总天数, 月份不合法时返回 None
+pub fn days_in_month(year: i32, month: u32) -> Option<u32> {
+    match month {
+        4 | 6 | 9 | 11 => Some(30),
+        2 if is_leap_year(year) => Some(29),
+        2 => Some(28),
+        1..=12 => Some(31),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, DateError> {
+        if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+            return Err(DateError::YearOutOfRange(year as i64));
+        }
+        let days = days_in_month(year, month).ok_or(DateError::InvalidMonth(month))?;
+        if day == 0 || day > days {
+            return Err(DateError::InvalidDay { year, month, day });
+        }
+        Ok(Date { year, month: month as u8, day: day as u8 })
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month as u32
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day as u32
+    }
+
+    /// 距 1970-01-01 的天数
+    pub fn day_number(&self) -> i64 {
+        // 以 3 月 1 日为一年的开始, 闰日落在年末
+        let (month, day) = (self.month as i64, self.day as i64);
+        let year = self.year as i64 - (month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);                                     // [0, 399]
+        let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;             // [0, 365]
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;                    // [0, 146096]
+        era * 146_097 + doe - 719_468
+    }
+
+    pub fn from_day_number(n: i64) -> Result<Self, DateError> {
+        let z = n.checked_add(719_468).ok_or(DateError::Overflow)?;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe.checked_add(era.checked_mul(400).ok_or(DateError::Overflow)?).ok_or(DateError::Overflow)?
+            + (month <= 2) as i64;
+        if !(MIN_YEAR as i64..=MAX_YEAR as i64).contains(&year) {
+            return Err(DateError::Overflow);
+        }
+        Ok(Date { year: year as i32, month: month as u8, day: day as u8 })
+    }
+
+    pub fn add_days(&self, days: i64) -> Result<Self, DateError> {
+        Date::from_day_number(self.day_number().checked_add(days).ok_or(DateError::Overflow)?)
+    }
+
+    pub fn sub_days(&self, days: i64) -> Result<Self, DateError> {
+        Date::from_day_number(self.day_number().checked_sub(days).ok_or(DateError::Overflow)?)
+    }
+
+    /// 儒略日数 (Julian Day Number), 即该日正午的儒略日
+    pub fn jdn(&self) -> i64 {
+        self.day_number() + UNIX_EPOCH_JDN
+    }
+
+    pub fn from_jdn(jdn: i64) -> Result<Self, DateError> {
+        Date::from_day_number(jdn.checked_sub(UNIX_EPOCH_JDN).ok_or(DateError::Overflow)?)
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        // 1970-01-01 为星期四
+        let n = (self.day_number() + 3).rem_euclid(7) as u32 + 1;
+        Weekday::from_monday(n).unwrap()
+    }
+
+    /// 一年中的第几天, 1 月 1 日为 1
+    pub fn ordinal(&self) -> u32 {
+        (1..self.month()).map(|m| days_in_month(self.year, m).unwrap()).sum::<u32>() + self.day()
+    }
+
+    /// ISO 8601 周历 (周年, 周数, 星期), 周年可能与公历年份相差一年
+    pub fn iso_week(&self) -> (i32, u32, Weekday) {
+        let weekday = self.weekday();
+        let week = (self.ordinal() as i64 - weekday.number_from_monday() as i64 + 10) / 7;
+        if week < 1 {
+            (self.year - 1, iso_weeks_in_year(self.year - 1), weekday)
+        } else if week as u32 > iso_weeks_in_year(self.year) {
+            (self.year + 1, 1, weekday)
+        } else {
+            (self.year, week as u32, weekday)
+        }
+    }
+}
+
+/// ISO 周年中的周数: 1 月 1 日为星期四, 或闰年 1 月 1 日为星期三时为 53 周, 否则为 52 周
+pub fn iso_weeks_in_year(year: i32) -> u32 {
+    // 超出范围的年份按 400 年周期折回
+    let jan1 = Date { year: year.rem_euclid(400), month: 1, day: 1 }.weekday();
+    match jan1 {
+        Weekday::Thursday => 53,
+        Weekday::Wednesday if is_leap_year(year) => 53,
+        _ => 52,
+    }
+}
+
+/// 两个日期之间相差的天数 (self - rhs)
+impl Sub for Date {
+    type Output = i64;
+
+    fn sub(self, rhs: Self) -> i64 {
+        self.day_number() - rhs.day_number()
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.year < 0 {
+            write!(f, "-{:04}-{:02}-{:02}", -self.year, self.month, self.day)
+        } else {
+            write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+        }
+    }
+}
+
+/// 解析 "YYYY-M-D", 月与日可以带前导零, 年份前可以带负号
+impl FromStr for Date {
+    type Err = DateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, month, day) = parse_ymd(s)?;
+        Date::new(year, month, day)
+    }
+}
+
+/// 将 "YYYY-M-D" 拆分为年月日, 不检查该日期在某种历法中是否存在
+pub(super) fn parse_ymd(s: &str) -> Result<(i32, u32, u32), DateError> {
+    let trimmed = s.trim();
+    let (negative, rest) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    let [year, month, day]: [&str; 3] = rest.split('-')
+        .collect::<Vec<&str>>()
+        .try_into()
+        .map_err(|_| DateError::Format(s.to_string()))?;
+
+    let number = |part: &str| -> Result<u64, DateError> {
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(DateError::InvalidNumber(part.to_string()));
+        }
+        // 位数过多的数字视为超出范围, 而不是解析失败
+        Ok(part.parse::<u64>().unwrap_or(u64::MAX))
+    };
+    let (y, m, d) = (number(year)?, number(month)?, number(day)?);
+
+    let y = i64::try_from(y).unwrap_or(i64::MAX);
+    let y = if negative { -y } else { y };
+    let year = i32::try_from(y).map_err(|_| DateError::YearOutOfRange(y))?;
+    let month = u32::try_from(m).map_err(|_| DateError::InvalidMonth(u32::MAX))?;
+    let day = u32::try_from(d).unwrap_or(u32::MAX);
+    Ok((year, month, day))
+}
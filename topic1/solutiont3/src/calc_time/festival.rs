@@ -0,0 +1,88 @@
+//! 传统节日
+//!
+//! 春节、端午、中秋、重阳按农历日期推算, 清明为清明节气当天
+//!
+//! 节日的日期范围与农历数据一致, 即 MIN_LUNAR_YEAR..=MAX_LUNAR_YEAR 年
+use std::fmt::{Display, Formatter};
+use super::Date;
+use super::lunar_cal::{LunarDate, LunarError, MAX_LUNAR_YEAR, MIN_LUNAR_YEAR};
+use super::solar_term::solar_term_date;
+
+/// SOLAR_TERMS 中清明的下标
+const QINGMING: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Festival {
+    LunarNewYear, // 春节, 正月初一
+    Qingming,       // 清明
+    DragonBoat,     // 端午, 五月初五
+    MidAutumn,      // 中秋, 八月十五
+    DoubleNinth,    // 重阳, 九月初九
+}
+
+impl Festival {
+    /// 按公历年内的顺序排列
+    pub const ALL: [Festival; 5] = [
+        Festival::LunarNewYear, Festival::Qingming, Festival::DragonBoat,
+        Festival::MidAutumn, Festival::DoubleNinth,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Festival::LunarNewYear => "春节",
+            Festival::Qingming => "清明",
+            Festival::DragonBoat => "端午",
+            Festival::MidAutumn => "中秋",
+            Festival::DoubleNinth => "重阳",
+        }
+    }
+
+    /// 公历 year 年中的节日日期
+    pub fn date_in(self, year: i32) -> Result<Date, LunarError> {
+        // 农历 year 年的正月初一到九月初九都落在公历 year 年之内
+        let (month, day) = match self {
+            Festival::LunarNewYear => (1, 1),
+            Festival::DragonBoat => (5, 5),
+            Festival::MidAutumn => (8, 15),
+            Festival::DoubleNinth => (9, 9),
+            Festival::Qingming => {
+                if !(MIN_LUNAR_YEAR..=MAX_LUNAR_YEAR).contains(&year) {
+                    return Err(LunarError::YearOutOfRange(year));
+                }
+                // 节气的年份范围与农历数据相同
+                return solar_term_date(year, QINGMING).map_err(|_| LunarError::YearOutOfRange(year));
+            }
+        };
+        Ok(LunarDate::new(year, month, false, day)?.to_solar())
+    }
+
+    /// date 之后 (不含当天) 最近的一次节日
+    pub fn next_after(self, date: Date) -> Result<Date, LunarError> {
+        let this_year = self.date_in(date.year())?;
+        if this_year > date {
+            Ok(this_year)
+        } else {
+            self.date_in(date.year() + 1)
+        }
+    }
+
+    /// 距下一次节日的剩余天数, 与 date_info 相同不计当天与节日当天, 明天即为节日时返回 0
+    pub fn days_until(self, date: Date) -> Result<i64, LunarError> {
+        Ok(self.next_after(date)? - date - 1)
+    }
+}
+
+impl Display for Festival {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// 全部节日的 (节日, 下一次的日期, 剩余天数), 按下一次的日期排序, 剩余天数的含义同 days_until
+pub fn upcoming(date: Date) -> Result<Vec<(Festival, Date, i64)>, LunarError> {
+    let mut res = Festival::ALL.iter()
+        .map(|&festival| festival.next_after(date).map(|next| (festival, next, next - date - 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    res.sort_by_key(|&(_, next, _)| next);
+    Ok(res)
+}
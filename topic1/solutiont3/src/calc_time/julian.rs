@@ -0,0 +1,229 @@
+//! 儒略历与历史上的改历
+//!
+//! # 主要特点:
+//! - 以儒略日数 (JDN) 为统一的日序号, 公历 (Date) 与儒略历 (JulianDate) 之间经由 JDN 换算
+//! - 两种历法都是预推的 (proleptic), 天文纪年: 0 年即公元前 1 年, 儒略历中 0 年为闰年
+//! - Changeover 描述一次改历: 改历日 (公历) 之前按儒略历计, 从改历日起按公历计,
+//!   默认为 1582-10-15, 即儒略历 1582-10-04 的次日, 中间的 10 天在历史日历中不存在
+//!
+//! # 参考文献:
+//! - https://en.wikipedia.org/wiki/Julian_day
+//! - https://en.wikipedia.org/wiki/Proleptic_Julian_calendar
+//! - Richards, E. G., Calendars, in Explanatory Supplement to the Astronomical Almanac, 3rd ed., 2013
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use super::date::{parse_ymd, Weekday, MAX_YEAR, MIN_YEAR};
+use super::{Date, DateError};
+
+/// 儒略历闰年: 能被 4 整除的年份
+pub fn is_julian_leap_year(year: i32) -> bool {
+    year.rem_euclid(4) == 0
+}
+
+/// 儒略历某年某月的总天数, 月份不合法时返回 None
+pub fn julian_days_in_month(year: i32, month: u32) -> Option<u32> {
+    match month {
+        4 | 6 | 9 | 11 => Some(30),
+        2 if is_julian_leap_year(year) => Some(29),
+        2 => Some(28),
+        1..=12 => Some(31),
+        _ => None,
+    }
+}
+
+/// 预推儒略历中的日期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JulianDate {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl JulianDate {
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, DateError> {
+        if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+            return Err(DateError::YearOutOfRange(year as i64));
+        }
+        let days = julian_days_in_month(year, month).ok_or(DateError::InvalidMonth(month))?;
+        if day == 0 || day > days {
+            return Err(DateError::InvalidDay { year, month, day });
+        }
+        Ok(JulianDate { year, month: month as u8, day: day as u8 })
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month as u32
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day as u32
+    }
+
+    pub fn jdn(&self) -> i64 {
+        // 以 3 月 1 日为一年的开始, 以 -4800 年为起点使被除数非负
+        let a = (14 - self.month as i64) / 12;
+        let y = self.year as i64 + 4800 - a;
+        let m = self.month as i64 + 12 * a - 3;
+        self.day as i64 + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4) - 32_083
+    }
+
+    pub fn from_jdn(jdn: i64) -> Result<Self, DateError> {
+        let c = jdn.checked_add(32_082).ok_or(DateError::Overflow)?;
+        let d = c.checked_mul(4).and_then(|c| c.checked_add(3)).ok_or(DateError::Overflow)?.div_euclid(1461);
+        let e = c - (1461 * d).div_euclid(4);
+        let m = (5 * e + 2) / 153;
+        let day = e - (153 * m + 2) / 5 + 1;
+        let month = m + 3 - 12 * (m / 10);
+        let year = d - 4800 + m / 10;
+        if !(MIN_YEAR as i64..=MAX_YEAR as i64).contains(&year) {
+            return Err(DateError::Overflow);
+        }
+        Ok(JulianDate { year: year as i32, month: month as u8, day: day as u8 })
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        // JDN 0 为星期一
+        Weekday::from_monday(self.jdn().rem_euclid(7) as u32 + 1).unwrap()
+    }
+
+    pub fn to_gregorian(self) -> Result<Date, DateError> {
+        Date::from_jdn(self.jdn())
+    }
+
+    pub fn from_gregorian(date: Date) -> Result<Self, DateError> {
+        JulianDate::from_jdn(date.jdn())
+    }
+}
+
+impl Display for JulianDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.year < 0 {
+            write!(f, "-{:04}-{:02}-{:02}", -self.year, self.month, self.day)
+        } else {
+            write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+        }
+    }
+}
+
+/// 与 Date 相同的 "YYYY-M-D" 格式, 按儒略历校验
+impl FromStr for JulianDate {
+    type Err = DateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, month, day) = parse_ymd(s)?;
+        JulianDate::new(year, month, day)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Calendar {
+    Julian,
+    Gregorian,
+}
+
+/// 历史日历中的日期, 改历日之前为儒略历, 之后为公历
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HistoricalDate {
+    Julian(JulianDate),
+    Gregorian(Date),
+}
+
+impl HistoricalDate {
+    pub fn calendar(&self) -> Calendar {
+        match self {
+            HistoricalDate::Julian(_) => Calendar::Julian,
+            HistoricalDate::Gregorian(_) => Calendar::Gregorian,
+        }
+    }
+
+    pub fn jdn(&self) -> i64 {
+        match self {
+            HistoricalDate::Julian(date) => date.jdn(),
+            HistoricalDate::Gregorian(date) => date.jdn(),
+        }
+    }
+
+    pub fn ymd(&self) -> (i32, u32, u32) {
+        match self {
+            HistoricalDate::Julian(date) => (date.year(), date.month(), date.day()),
+            HistoricalDate::Gregorian(date) => (date.year(), date.month(), date.day()),
+        }
+    }
+}
+
+impl Display for HistoricalDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoricalDate::Julian(date) => write!(f, "{date}"),
+            HistoricalDate::Gregorian(date) => write!(f, "{date}"),
+        }
+    }
+}
+
+/// 从儒略历改用公历的时刻
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Changeover {
+    /// 第一个按公历计的日期
+    first_gregorian: Date,
+}
+
+impl Default for Changeover {
+    /// 1582-10-15, 教皇格里高利十三世颁布的改历日
+    fn default() -> Self {
+        Changeover { first_gregorian: Date::new(1582, 10, 15).unwrap() }
+    }
+}
+
+impl Changeover {
+    /// 例如英国及其殖民地为 1752-09-14
+    pub fn new(first_gregorian: Date) -> Self {
+        Changeover { first_gregorian }
+    }
+
+    pub fn first_gregorian(&self) -> Date {
+        self.first_gregorian
+    }
+
+    /// 最后一个按儒略历计的日期
+    pub fn last_julian(&self) -> Result<JulianDate, DateError> {
+        JulianDate::from_jdn(self.first_gregorian.jdn() - 1)
+    }
+
+    pub fn calendar_of(&self, jdn: i64) -> Calendar {
+        if jdn < self.first_gregorian.jdn() {
+            Calendar::Julian
+        } else {
+            Calendar::Gregorian
+        }
+    }
+
+    pub fn date_at(&self, jdn: i64) -> Result<HistoricalDate, DateError> {
+        match self.calendar_of(jdn) {
+            Calendar::Julian => JulianDate::from_jdn(jdn).map(HistoricalDate::Julian),
+            Calendar::Gregorian => Date::from_jdn(jdn).map(HistoricalDate::Gregorian),
+        }
+    }
+
+    /// 历史日历中的 (year, month, day), 改历时跳过的日期返回 InvalidDay
+    pub fn date(&self, year: i32, month: u32, day: u32) -> Result<HistoricalDate, DateError> {
+        let first = &self.first_gregorian;
+        if (year, month, day) >= (first.year(), first.month(), first.day()) {
+            return Date::new(year, month, day).map(HistoricalDate::Gregorian);
+        }
+        let date = JulianDate::new(year, month, day)?;
+        if date.jdn() >= first.jdn() {
+            return Err(DateError::InvalidDay { year, month, day });
+        }
+        Ok(HistoricalDate::Julian(date))
+    }
+
+    /// 按历史日历解析 "YYYY-M-D"
+    pub fn parse(&self, s: &str) -> Result<HistoricalDate, DateError> {
+        let (year, month, day) = parse_ymd(s)?;
+        self.date(year, month, day)
+    }
+}
@@ -0,0 +1,317 @@
+//! 农历 (阴阳合历) 与公历的互相转换
+//!
+//! # 主要特点:
+//! - 覆盖农历 1900 年正月初一 (1900-01-31) 至农历 2100 年腊月 (2101-01-28)
+//! - 支持闰月, 天干地支纪年/月/日, 生肖, 中文格式 (如 甲辰年 腊月 初八)
+//! - 年干支与生肖以春节为界; 月干支按月建推算 (正月建寅, 五虎遁), 闰月与所闰之月相同
+//!
+//! # 参考文献:
+//! - https://www.hko.gov.hk/tc/gts/time/conversion.htm
+//! - https://zh.wikipedia.org/wiki/%E5%B9%B2%E6%94%AF
+use std::fmt::{Display, Formatter};
+use super::{Date, DateError};
+
+/// DATA FROM https://blog.csdn.net/qq_41146650/article/details/102876334
+/// 使用比特位记录每年的情况
+/// 0~4 共5bit 春节日份
+/// 5~6 共2bit 春节月份
+/// 7~19 共13bit 13个月的大小月情况(如果无闰月，最后位无效)，大月为1（30天）,小月为0（29天）
+/// 20~23 共4bit 记录闰月的月份，如果没有闰月为0
+const LUNAR_NEW_YEAR: [u32; 199] = [
+    0x04AE53, 0x0A5748, 0x5526BD, 0x0D2650, 0x0D9544, 0x46AAB9, 0x056A4D, 0x09AD42, 0x24AEB6, 0x04AE4A, /*1901-1910*/
+    0x6A4DBE, 0x0A4D52, 0x0D2546, 0x5D52BA, 0x0B544E, 0x0D6A43, 0x296D37, 0x095B4B, 0x749BC1, 0x049754, /*1911-1920*/
+    0x0A4B48, 0x5B25BC, 0x06A550, 0x06D445, 0x4ADAB8, 0x02B64D, 0x095742, 0x2497B7, 0x04974A, 0x664B3E, /*1921-1930*/
+    0x0D4A51, 0x0EA546, 0x56D4BA, 0x05AD4E, 0x02B644, 0x393738, 0x092E4B, 0x7C96BF, 0x0C9553, 0x0D4A48, /*1931-1940*/
+    0x6DA53B, 0x0B554F, 0x056A45, 0x4AADB9, 0x025D4D, 0x092D42, 0x2C95B6, 0x0A954A, 0x7B4ABD, 0x06CA51, /*1941-1950*/
+    0x0B5546, 0x555ABB, 0x04DA4E, 0x0A5B43, 0x352BB8, 0x052B4C, 0x8A953F, 0x0E9552, 0x06AA48, 0x6AD53C, /*1951-1960*/
+    0x0AB54F, 0x04B645, 0x4A5739, 0x0A574D, 0x052642, 0x3E9335, 0x0D9549, 0x75AABE, 0x056A51, 0x096D46, /*1961-1970*/
+    0x54AEBB, 0x04AD4F, 0x0A4D43, 0x4D26B7, 0x0D254B, 0x8D52BF, 0x0B5452, 0x0B6A47, 0x696D3C, 0x095B50, /*1971-1980*/
+    0x049B45, 0x4A4BB9, 0x0A4B4D, 0xAB25C2, 0x06A554, 0x06D449, 0x6ADA3D, 0x0AB651, 0x093746, 0x5497BB, /*1981-1990*/
+    0x04974F, 0x064B44, 0x36A537, 0x0EA54A, 0x86B2BF, 0x05AC53, 0x0AB647, 0x5936BC, 0x092E50, 0x0C9645, /*1991-2000*/
+    0x4D4AB8, 0x0D4A4C, 0x0DA541, 0x25AAB6, 0x056A49, 0x7AADBD, 0x025D52, 0x092D47, 0x5C95BA, 0x0A954E, /*2001-2010*/
+    0x0B4A43, 0x4B5537, 0x0AD54A, 0x955ABF, 0x04BA53, 0x0A5B48, 0x652BBC, 0x052B50, 0x0A9345, 0x474AB9, /*2011-2020*/
+    0x06AA4C, 0x0AD541, 0x24DAB6, 0x04B64A, 0x69573D, 0x0A4E51, 0x0D2646, 0x5E933A, 0x0D534D, 0x05AA43, /*2021-2030*/
+    0x36B537, 0x096D4B, 0xB4AEBF, 0x04AD53, 0x0A4D48, 0x6D25BC, 0x0D254F, 0x0D5244, 0x5DAA38, 0x0B5A4C, /*2031-2040*/
+    0x056D41, 0x24ADB6, 0x049B4A, 0x7A4BBE, 0x0A4B51, 0x0AA546, 0x5B52BA, 0x06D24E, 0x0ADA42, 0x355B37, /*2041-2050*/
+    0x09374B, 0x8497C1, 0x049753, 0x064B48, 0x66A53C, 0x0EA54F, 0x06B244, 0x4AB638, 0x0AAE4C, 0x092E42, /*2051-2060*/
+    0x3C9735, 0x0C9649, 0x7D4ABD, 0x0D4A51, 0x0DA545, 0x55AABA, 0x056A4E, 0x0A6D43, 0x452EB7, 0x052D4B, /*2061-2070*/
+    0x8A95BF, 0x0A9553, 0x0B4A47, 0x6B553B, 0x0AD54F, 0x055A45, 0x4A5D38, 0x0A5B4C, 0x052B42, 0x3A93B6, /*2071-2080*/
+    0x069349, 0x7729BD, 0x06AA51, 0x0AD546, 0x54DABA, 0x04B64E, 0x0A5743, 0x452738, 0x0D264A, 0x8E933E, /*2081-2090*/
+    0x0D5252, 0x0DAA47, 0x66B53B, 0x056D4F, 0x04AE45, 0x4A4EB9, 0x0A4D4C, 0x0D1541, 0x2D92B5            /*2091-2099*/
+];
+const START_YEAR: i32 = 1901;
+
+/// 获取公历某一年对应的农历大年初一的时间, 仅支持 1901-2099 年
+pub fn get_lunar_new_year_date(year: i32) -> Result<Date, DateError> {
+    if !(START_YEAR..START_YEAR + LUNAR_NEW_YEAR.len() as i32).contains(&year) {
+        return Err(DateError::YearOutOfRange(year as i64));
+    }
+
+    let abs_year = (year - START_YEAR) as usize;
+    Date::new(
+        year,
+        (LUNAR_NEW_YEAR[abs_year] >> 5) & 0x03,
+        LUNAR_NEW_YEAR[abs_year] & 0x1f
+    )
+}
+
+/// 农历 1900-2100 年的月份信息
+/// 0~3 共4bit 闰月的月份, 没有闰月为0
+/// 4~15 共12bit 正月至腊月的大小月情况, 正月在最高位, 大月为1（30天）, 小月为0（29天）
+/// 16 共1bit 闰月的大小
+///
+/// 与 LUNAR_NEW_YEAR 相比, 1989, 2025, 2060, 2089, 2097 年个别月份的大小不同 (春节与闰月一致),
+/// 以香港天文台的数据为准
+const LUNAR_INFO: [u32; 201] = [
+    0x04bd8, 0x04ae0, 0x0a570, 0x054d5, 0x0d260, 0x0d950, 0x16554, 0x056a0, 0x09ad0, 0x055d2, /*1900-1909*/
+    0x04ae0, 0x0a5b6, 0x0a4d0, 0x0d250, 0x1d255, 0x0b540, 0x0d6a0, 0x0ada2, 0x095b0, 0x14977, /*1910-1919*/
+    0x04970, 0x0a4b0, 0x0b4b5, 0x06a50, 0x06d40, 0x1ab54, 0x02b60, 0x09570, 0x052f2, 0x04970, /*1920-1929*/
+    0x06566, 0x0d4a0, 0x0ea50, 0x16a95, 0x05ad0, 0x02b60, 0x186e3, 0x092e0, 0x1c8d7, 0x0c950, /*1930-1939*/
+    0x0d4a0, 0x1d8a6, 0x0b550, 0x056a0, 0x1a5b4, 0x025d0, 0x092d0, 0x0d2b2, 0x0a950, 0x0b557, /*1940-1949*/
+    0x06ca0, 0x0b550, 0x15355, 0x04da0, 0x0a5b0, 0x14573, 0x052b0, 0x0a9a8, 0x0e950, 0x06aa0, /*1950-1959*/
+    0x0aea6, 0x0ab50, 0x04b60, 0x0aae4, 0x0a570, 0x05260, 0x0f263, 0x0d950, 0x05b57, 0x056a0, /*1960-1969*/
+    0x096d0, 0x04dd5, 0x04ad0, 0x0a4d0, 0x0d4d4, 0x0d250, 0x0d558, 0x0b540, 0x0b6a0, 0x195a6, /*1970-1979*/
+    0x095b0, 0x049b0, 0x0a974, 0x0a4b0, 0x0b27a, 0x06a50, 0x06d40, 0x0af46, 0x0ab60, 0x09570, /*1980-1989*/
+    0x04af5, 0x04970, 0x064b0, 0x074a3, 0x0ea50, 0x06b58, 0x05ac0, 0x0ab60, 0x096d5, 0x092e0, /*1990-1999*/
+    0x0c960, 0x0d954, 0x0d4a0, 0x0da50, 0x07552, 0x056a0, 0x0abb7, 0x025d0, 0x092d0, 0x0cab5, /*2000-2009*/
+    0x0a950, 0x0b4a0, 0x0baa4, 0x0ad50, 0x055d9, 0x04ba0, 0x0a5b0, 0x15176, 0x052b0, 0x0a930, /*2010-2019*/
+    0x07954, 0x06aa0, 0x0ad50, 0x05b52, 0x04b60, 0x0a6e6, 0x0a4e0, 0x0d260, 0x0ea65, 0x0d530, /*2020-2029*/
+    0x05aa0, 0x076a3, 0x096d0, 0x04afb, 0x04ad0, 0x0a4d0, 0x1d0b6, 0x0d250, 0x0d520, 0x0dd45, /*2030-2039*/
+    0x0b5a0, 0x056d0, 0x055b2, 0x049b0, 0x0a577, 0x0a4b0, 0x0aa50, 0x1b255, 0x06d20, 0x0ada0, /*2040-2049*/
+    0x14b63, 0x09370, 0x049f8, 0x04970, 0x064b0, 0x168a6, 0x0ea50, 0x06b20, 0x1a6c4, 0x0aae0, /*2050-2059*/
+    0x0a2e0, 0x0d2e3, 0x0c960, 0x0d557, 0x0d4a0, 0x0da50, 0x05d55, 0x056a0, 0x0a6d0, 0x055d4, /*2060-2069*/
+    0x052d0, 0x0a9b8, 0x0a950, 0x0b4a0, 0x0b6a6, 0x0ad50, 0x055a0, 0x0aba4, 0x0a5b0, 0x052b0, /*2070-2079*/
+    0x0b273, 0x06930, 0x07337, 0x06aa0, 0x0ad50, 0x14b55, 0x04b60, 0x0a570, 0x054e4, 0x0d160, /*2080-2089*/
+    0x0e968, 0x0d520, 0x0daa0, 0x16aa6, 0x056d0, 0x04ae0, 0x0a9d4, 0x0a2d0, 0x0d150, 0x0f252, /*2090-2099*/
+    0x0d520                                                                                    /*2100*/
+];
+pub const MIN_LUNAR_YEAR: i32 = 1900;
+pub const MAX_LUNAR_YEAR: i32 = 2100;
+
+const STEMS: [&str; 10] = ["甲", "乙", "丙", "丁", "戊", "己", "庚", "辛", "壬", "癸"];
+const BRANCHES: [&str; 12] = ["子", "丑", "寅", "卯", "辰", "巳", "午", "未", "申", "酉", "戌", "亥"];
+const ZODIAC: [&str; 12] = ["鼠", "牛", "虎", "兔", "龙", "蛇", "马", "羊", "猴", "鸡", "狗", "猪"];
+const MONTH_NAMES: [&str; 12] = ["正", "二", "三", "四", "五", "六", "七", "八", "九", "十", "冬", "腊"];
+const DIGITS: [&str; 10] = ["十", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LunarError {
+    YearOutOfRange(i32),                            // 不在 MIN_LUNAR_YEAR..=MAX_LUNAR_YEAR
+    DateOutOfRange(Date),                           // 公历日期超出农历数据覆盖的范围
+    InvalidMonth(u32),                              // 月份不在 1..=12
+    NoLeapMonth { year: i32, month: u32 },          // 该年没有这个闰月
+    InvalidDay { year: i32, month: u32, leap: bool, day: u32 },
+}
+
+impl Display for LunarError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LunarError::YearOutOfRange(year) => write!(f, "lunar year {year} is out of range {MIN_LUNAR_YEAR}..={MAX_LUNAR_YEAR}"),
+            LunarError::DateOutOfRange(date) => write!(f, "{date} is out of the supported lunar range"),
+            LunarError::InvalidMonth(month) => write!(f, "lunar month {month} is out of range 1..=12"),
+            LunarError::NoLeapMonth { year, month } => write!(f, "lunar year {year} has no leap month {month}"),
+            LunarError::InvalidDay { year, month, leap, day } => {
+                let leap = if *leap { "leap " } else { "" };
+                write!(f, "lunar {year} {leap}month {month} has no day {day}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LunarError { }
+
+/// 六十甲子中的一个, 0 为甲子
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GanZhi(u8);
+
+impl GanZhi {
+    /// 任意整数按 60 取模
+    pub fn from_index(index: i64) -> Self {
+        GanZhi(index.rem_euclid(60) as u8)
+    }
+
+    /// 天干, 0 为甲
+    pub fn stem(self) -> u32 {
+        self.0 as u32 % 10
+    }
+
+    /// 地支, 0 为子
+    pub fn branch(self) -> u32 {
+        self.0 as u32 % 12
+    }
+}
+
+impl Display for GanZhi {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", STEMS[self.stem() as usize], BRANCHES[self.branch() as usize])
+    }
+}
+
+fn info(year: i32) -> u32 {
+    LUNAR_INFO[(year - MIN_LUNAR_YEAR) as usize]
+}
+
+/// 农历某年的闰月, 没有闰月时返回 None
+pub fn leap_month(year: i32) -> Option<u32> {
+    if !(MIN_LUNAR_YEAR..=MAX_LUNAR_YEAR).contains(&year) {
+        return None;
+    }
+    Some(info(year) & 0xf).filter(|&m| m != 0)
+}
+
+/// 农历某年某月的天数 (29 或 30), leap 为 true 时表示该月之后的闰月
+pub fn month_days(year: i32, month: u32, leap: bool) -> Result<u32, LunarError> {
+    if !(MIN_LUNAR_YEAR..=MAX_LUNAR_YEAR).contains(&year) {
+        return Err(LunarError::YearOutOfRange(year));
+    }
+    if !(1..=12).contains(&month) {
+        return Err(LunarError::InvalidMonth(month));
+    }
+    if leap {
+        if leap_month(year) != Some(month) {
+            return Err(LunarError::NoLeapMonth { year, month });
+        }
+        return Ok(29 + ((info(year) >> 16) & 1));
+    }
+    Ok(29 + ((info(year) >> (16 - month)) & 1))
+}
+
+/// 农历某年的总天数
+pub fn year_days(year: i32) -> Result<u32, LunarError> {
+    let mut days = 0;
+    for month in 1..=12 {
+        days += month_days(year, month, false)?;
+    }
+    if let Some(month) = leap_month(year) {
+        days += month_days(year, month, true)?;
+    }
+    Ok(days)
+}
+
+/// 按时间顺序排列的 (月份, 是否闰月, 天数)
+fn months_of(year: i32) -> impl Iterator<Item = (u32, bool, u32)> {
+    let leap = leap_month(year);
+    (1..=12).flat_map(move |month| {
+        let regular = Some((month, false, 29 + ((info(year) >> (16 - month)) & 1)));
+        let leap = (leap == Some(month)).then(|| (month, true, 29 + ((info(year) >> 16) & 1)));
+        regular.into_iter().chain(leap)
+    })
+}
+
+/// 农历日期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LunarDate {
+    year: i32,
+    month: u32,
+    leap: bool,
+    day: u32,
+}
+
+impl LunarDate {
+    /// 农历 1900 年正月初一对应的公历日期
+    fn epoch() -> Date {
+        Date::new(1900, 1, 31).unwrap()
+    }
+
+    pub fn new(year: i32, month: u32, leap: bool, day: u32) -> Result<Self, LunarError> {
+        let days = month_days(year, month, leap)?;
+        if day == 0 || day > days {
+            return Err(LunarError::InvalidDay { year, month, leap, day });
+        }
+        Ok(LunarDate { year, month, leap, day })
+    }
+
+    pub fn from_solar(date: Date) -> Result<Self, LunarError> {
+        let mut offset = date - Self::epoch();
+        if offset < 0 {
+            return Err(LunarError::DateOutOfRange(date));
+        }
+        for year in MIN_LUNAR_YEAR..=MAX_LUNAR_YEAR {
+            let days = year_days(year)? as i64;
+            if offset >= days {
+                offset -= days;
+                continue;
+            }
+            for (month, leap, days) in months_of(year) {
+                if offset < days as i64 {
+                    return Ok(LunarDate { year, month, leap, day: offset as u32 + 1 });
+                }
+                offset -= days as i64;
+            }
+        }
+        Err(LunarError::DateOutOfRange(date))
+    }
+
+    pub fn to_solar(self) -> Date {
+        let mut offset: i64 = (MIN_LUNAR_YEAR..self.year).map(|y| year_days(y).unwrap() as i64).sum();
+        offset += months_of(self.year)
+            .take_while(|&(month, leap, _)| (month, leap) != (self.month, self.leap))
+            .map(|(_, _, days)| days as i64)
+            .sum::<i64>();
+        Self::epoch().add_days(offset + self.day as i64 - 1).unwrap()
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn is_leap_month(&self) -> bool {
+        self.leap
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// 年干支, 以春节为界 (1984 年为甲子年)
+    pub fn year_ganzhi(&self) -> GanZhi {
+        GanZhi::from_index(self.year as i64 - 1984)
+    }
+
+    /// 月干支: 正月建寅, 月干由年干按五虎遁推出 (甲己之年丙作首), 闰月与所闰之月相同
+    pub fn month_ganzhi(&self) -> GanZhi {
+        let stem = (self.year_ganzhi().stem() % 5 * 2 + 2 + self.month - 1) % 10;
+        let branch = (2 + self.month - 1) % 12;
+        // 由天干与地支求六十甲子序号: 满足 i % 10 == stem, i % 12 == branch
+        GanZhi((0..60).find(|i| i % 10 == stem && i % 12 == branch).unwrap() as u8)
+    }
+
+    /// 日干支, 1970-01-01 为辛巳日
+    pub fn day_ganzhi(&self) -> GanZhi {
+        GanZhi::from_index(self.to_solar().day_number() + 17)
+    }
+
+    /// 生肖
+    pub fn zodiac(&self) -> &'static str {
+        ZODIAC[self.year_ganzhi().branch() as usize]
+    }
+
+    /// 中文月份, 如 正月, 闰六月, 腊月
+    pub fn month_name(&self) -> String {
+        format!("{}{}月", if self.leap { "闰" } else { "" }, MONTH_NAMES[self.month as usize - 1])
+    }
+
+    /// 中文日期, 如 初一, 十五, 廿三, 三十
+    pub fn day_name(&self) -> String {
+        match self.day {
+            10 => "初十".to_string(),
+            20 => "二十".to_string(),
+            30 => "三十".to_string(),
+            day => {
+                let prefix = ["初", "十", "廿"][(day / 10) as usize];
+                format!("{prefix}{}", DIGITS[(day % 10) as usize])
+            }
+        }
+    }
+}
+
+/// 例如 甲辰年 腊月 初八
+impl Display for LunarDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}年 {} {}", self.year_ganzhi(), self.month_name(), self.day_name())
+    }
+}
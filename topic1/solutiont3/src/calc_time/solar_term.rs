@@ -0,0 +1,138 @@
+//! 二十四节气
+//!
+//! 节气为太阳视黄经每达到 15° 的整数倍的时刻, 日期按北京时间 (UTC+8) 计;
+//! 只支持 MIN_SOLAR_TERM_YEAR..=MAX_SOLAR_TERM_YEAR 年, 在此范围内精确到日
+//!
+//! # 实现原理:
+//! - 太阳视黄经采用 Meeus 的低精度公式 (误差约 0.01°, 约合 15 分钟)
+//! - 以平均日长为步长做牛顿迭代, 求出黄经恰为目标值的儒略日 (力学时)
+//! - 减去 ΔT 得到世界时, 再加 8 小时换算为北京时间
+//!
+//! # 参考文献:
+//! - Jean Meeus, Astronomical Algorithms, 2nd ed., Chapter 25
+//! - https://eclipse.gsfc.nasa.gov/SEhelp/deltatpoly2004.html
+use std::fmt::{Display, Formatter};
+use super::Date;
+
+/// 按公历年内的顺序排列, 小寒为第一个
+pub const SOLAR_TERMS: [&str; 24] = [
+    "小寒", "大寒", "立春", "雨水", "惊蛰", "春分", "清明", "谷雨", "立夏", "小满", "芒种", "夏至",
+    "小暑", "大暑", "立秋", "处暑", "白露", "秋分", "寒露", "霜降", "立冬", "小雪", "大雪", "冬至",
+];
+
+pub const MIN_SOLAR_TERM_YEAR: i32 = 1900;
+pub const MAX_SOLAR_TERM_YEAR: i32 = 2100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolarTermError {
+    YearOutOfRange(i32),    // 不在 MIN_SOLAR_TERM_YEAR..=MAX_SOLAR_TERM_YEAR
+    InvalidIndex(usize),    // 节气下标不在 0..24
+}
+
+impl Display for SolarTermError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolarTermError::YearOutOfRange(year) =>
+                write!(f, "solar terms of {year} are out of range {MIN_SOLAR_TERM_YEAR}..={MAX_SOLAR_TERM_YEAR}"),
+            SolarTermError::InvalidIndex(index) => write!(f, "solar term index {index} is out of range 0..24"),
+        }
+    }
+}
+
+impl std::error::Error for SolarTermError { }
+
+/// 1970-01-01 0 时 (世界时) 的儒略日
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+/// J2000.0 的儒略日
+const J2000: f64 = 2_451_545.0;
+const TROPICAL_YEAR: f64 = 365.242_189;
+
+/// 太阳视黄经 (度, [0, 360)), jde 为力学时儒略日
+fn apparent_longitude(jde: f64) -> f64 {
+    let t = (jde - J2000) / 36_525.0;
+    let l0 = 280.466_46 + 36_000.769_83 * t + 0.000_303_2 * t * t;
+    let m = (357.529_11 + 35_999.050_29 * t - 0.000_153_7 * t * t).to_radians();
+    let c = (1.914_602 - 0.004_817 * t - 0.000_014 * t * t) * m.sin()
+        + (0.019_993 - 0.000_101 * t) * (2.0 * m).sin()
+        + 0.000_289 * (3.0 * m).sin();
+    // 章动与光行差修正
+    let omega = (125.04 - 1934.136 * t).to_radians();
+    (l0 + c - 0.005_69 - 0.004_78 * omega.sin()).rem_euclid(360.0)
+}
+
+/// 力学时与世界时之差 ΔT (秒), Espenak 与 Meeus 的分段多项式, 适用于 1900-2150 年
+fn delta_t(year: f64) -> f64 {
+    match year {
+        y if y < 1920.0 => {
+            let t = y - 1900.0;
+            -2.79 + 1.494_119 * t - 0.059_893_9 * t.powi(2) + 0.006_196_6 * t.powi(3) - 0.000_197 * t.powi(4)
+        }
+        y if y < 1941.0 => {
+            let t = y - 1920.0;
+            21.20 + 0.844_93 * t - 0.076_100 * t.powi(2) + 0.002_093_6 * t.powi(3)
+        }
+        y if y < 1961.0 => {
+            let t = y - 1950.0;
+            29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+        }
+        y if y < 1986.0 => {
+            let t = y - 1975.0;
+            45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+        }
+        y if y < 2005.0 => {
+            let t = y - 2000.0;
+            63.86 + 0.3345 * t - 0.060_374 * t.powi(2) + 0.001_727_5 * t.powi(3)
+                + 0.000_651_814 * t.powi(4) + 0.000_023_734_59 * t.powi(5)
+        }
+        y if y < 2050.0 => {
+            let t = y - 2000.0;
+            62.92 + 0.322_17 * t + 0.005_589 * t.powi(2)
+        }
+        y => -20.0 + 32.0 * ((y - 1820.0) / 100.0).powi(2) - 0.5628 * (2150.0 - y),
+    }
+}
+
+/// year 年第 index 个节气 (0 为小寒) 的北京时间日期
+pub fn solar_term_date(year: i32, index: usize) -> Result<Date, SolarTermError> {
+    if !(MIN_SOLAR_TERM_YEAR..=MAX_SOLAR_TERM_YEAR).contains(&year) {
+        return Err(SolarTermError::YearOutOfRange(year));
+    }
+    if index >= SOLAR_TERMS.len() {
+        return Err(SolarTermError::InvalidIndex(index));
+    }
+    let target = (285.0 + 15.0 * index as f64) % 360.0;
+    // 初值: 小寒约在 1 月 5 日, 之后每个节气约相隔 15.2 天
+    let jan1 = Date::new(year, 1, 1).expect("January 1st is valid").day_number() as f64 + UNIX_EPOCH_JD;
+    let mut jde = jan1 + 4.5 + TROPICAL_YEAR / 24.0 * index as f64;
+    for _ in 0..50 {
+        let diff = (target - apparent_longitude(jde) + 540.0) % 360.0 - 180.0;
+        jde += diff * TROPICAL_YEAR / 360.0;
+        if diff.abs() < 1e-7 {
+            break;
+        }
+    }
+
+    let jd_ut = jde - delta_t(year as f64 + index as f64 / 24.0) / 86_400.0;
+    let beijing = jd_ut + 8.0 / 24.0;
+    Ok(Date::from_day_number((beijing - UNIX_EPOCH_JD).floor() as i64).expect("solar terms stay within the year"))
+}
+
+/// year 年全部 24 个节气的 (名称, 日期), 按时间顺序
+pub fn solar_terms(year: i32) -> Result<Vec<(&'static str, Date)>, SolarTermError> {
+    SOLAR_TERMS.iter()
+        .enumerate()
+        .map(|(index, &name)| Ok((name, solar_term_date(year, index)?)))
+        .collect()
+}
+
+/// date 当天交节的节气名称
+pub fn solar_term_of(date: Date) -> Result<Option<&'static str>, SolarTermError> {
+    // 每个公历月恰有两个节气
+    let first = (date.month() as usize - 1) * 2;
+    for (index, &name) in SOLAR_TERMS.iter().enumerate().skip(first).take(2) {
+        if solar_term_date(date.year(), index)? == date {
+            return Ok(Some(name));
+        }
+    }
+    Ok(None)
+}
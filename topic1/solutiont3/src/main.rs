@@ -1,5 +1,3 @@
-use crate::calc_time::get_curr_week_since_this_year;
-
 mod calc_time;
 
 fn main() {
@@ -8,6 +6,116 @@ fn main() {
     println!("time info: {time_infos}");
     // println!("Week {}, left {} day(s) this year, {} day(s) after is spring festival", );
 
-    // println!("{}", get_curr_week_since_this_year(2024, 1, 7));
-    // println!("{}", get_curr_week_since_this_year(1993, 1, 1));
+    for input in ["2024-12-31", "2021-1-3", "-44-3-15", "2023-2-29", "2024-13-1"] {
+        match input.parse::<calc_time::Date>() {
+            Ok(date) => {
+                let (year, week, weekday) = date.iso_week();
+                println!(
+                    "{date}: day {}, ordinal {}, {year}-W{week:02}-{} (week {:?}), zeller {:?}, ±100 days = {:?} / {:?}",
+                    date.day_number(),
+                    date.ordinal(),
+                    weekday.number_from_monday(),
+                    calc_time::get_curr_week_since_this_year(date.year(), date.month(), date.day()),
+                    calc_time::zellers_kongruenz(date.year(), date.month(), date.day()),
+                    date.add_days(100).map(|d| d.to_string()),
+                    date.sub_days(100).map(|d| d.to_string())
+                );
+            }
+            Err(e) => println!("{input}: {e}"),
+        }
+    }
+
+    for input in ["2025-1-7", "2025-7-25", "1900-1-31", "2101-1-29"] {
+        let date: calc_time::Date = input.parse().unwrap();
+        match calc_time::lunar_cal::LunarDate::from_solar(date) {
+            Ok(lunar) => println!(
+                "{date}: {lunar} ({}年, {}月 {}日, 闰月 {:?}), 回到公历 {}",
+                lunar.zodiac(),
+                lunar.month_ganzhi(),
+                lunar.day_ganzhi(),
+                calc_time::lunar_cal::leap_month(lunar.year()),
+                lunar.to_solar()
+            ),
+            Err(e) => println!("{date}: {e}"),
+        }
+    }
+
+    // 农历转公历: 2025 年八月十五
+    match calc_time::lunar_cal::LunarDate::new(2025, 8, false, 15) {
+        Ok(lunar) => println!(
+            "农历 {} 年 {}{} 月 {} 日: {}",
+            lunar.year(),
+            if lunar.is_leap_month() { "闰" } else { "" },
+            lunar.month(),
+            lunar.day(),
+            lunar.to_solar()
+        ),
+        Err(e) => println!("{e}"),
+    }
+
+    // 节气与节日
+    let today: calc_time::Date = "2025-10-1".parse().unwrap();
+    match calc_time::solar_term::solar_terms(2025) {
+        Ok(terms) => {
+            let terms: Vec<String> = terms.iter()
+                .map(|(name, date)| format!("{name} {}", &date.to_string()[5..]))
+                .collect();
+            println!("2025 年节气: {}", terms.join(", "));
+        }
+        Err(e) => println!("{e}"),
+    }
+    println!("{today} 交节: {:?}", calc_time::solar_term::solar_term_of(today));
+    match calc_time::festival_info(today) {
+        Ok(info) => println!("{today} 距各节日: {info}"),
+        Err(e) => println!("{e}"),
+    }
+    for festival in calc_time::festival::Festival::ALL {
+        println!("{festival}: {:?}, {:?} 天后", festival.next_after(today).map(|d| d.to_string()), festival.days_until(today));
+    }
+
+    // 工作日
+    let holidays = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("holidays.txt");
+    match calc_time::business_day::HolidayCalendar::load(&holidays) {
+        Ok(calendar) => {
+            for input in ["2025-1-26", "2025-1-29", "2025-2-8", "2025-3-1"] {
+                let date: calc_time::Date = input.parse().unwrap();
+                println!("{date}: 上班 {}, 节日 {:?}", calendar.is_working_day(date), calendar.holiday(date));
+            }
+            let start: calc_time::Date = "2025-9-30".parse().unwrap();
+            println!("{start} 之后第 3 个工作日: {:?}", calendar.add_working_days(start, 3).map(|d| d.to_string()));
+            for year in [2024, 2025] {
+                let (first, next) = (calc_time::Date::new(year, 1, 1).unwrap(), calc_time::Date::new(year + 1, 1, 1).unwrap());
+                println!("{year} 年工作日: {}", calendar.working_days_between(first, next));
+            }
+        }
+        Err(e) => println!("{}: {e}", holidays.display()),
+    }
+
+    // 儒略历与改历
+    let changeover = calc_time::julian::Changeover::default();
+    for input in ["1582-10-4", "1582-10-10", "1582-10-15", "-44-3-15"] {
+        match changeover.parse(input) {
+            Ok(date) => println!("{input}: {:?} {:?} JDN {}, 预推公历 {:?}", date.calendar(), date.ymd(), date.jdn(),
+                calc_time::Date::from_jdn(date.jdn()).map(|d| d.to_string())),
+            Err(e) => println!("{input}: {e}"),
+        }
+    }
+
+    // 预推儒略历与公历互相换算
+    match "1918-1-31".parse::<calc_time::julian::JulianDate>() {
+        Ok(julian) => println!("儒略历 {julian} ({:?}): 公历 {:?}", julian.weekday(), julian.to_gregorian().map(|d| d.to_string())),
+        Err(e) => println!("{e}"),
+    }
+    println!("公历 {today}: 儒略历 {:?}", calc_time::julian::JulianDate::from_gregorian(today).map(|d| d.to_string()));
+
+    // 英国及其殖民地于 1752 年改历, 同一个 JDN 在两种历史日历中的日期不同
+    let britain = calc_time::julian::Changeover::new(calc_time::Date::new(1752, 9, 14).unwrap());
+    println!("英国改历: {:?} 之后为 {}", britain.last_julian().map(|d| d.to_string()), britain.first_gregorian());
+    let jdn = calc_time::Date::new(1700, 3, 1).unwrap().jdn();
+    for (name, changeover) in [("罗马", changeover), ("英国", britain)] {
+        match changeover.date_at(jdn) {
+            Ok(date) => println!("JDN {jdn} 在{name}为 {:?} {date}", changeover.calendar_of(jdn)),
+            Err(e) => println!("{e}"),
+        }
+    }
 }
@@ -38,4 +38,227 @@ mod tests {
         println!("Total score: {:.2}", total_score);
         assert_eq!(100.00, total_score);
     }
+
+    #[test]
+    fn test_date() {
+        use super::calc_time::date::{iso_weeks_in_year, Date, DateError, Weekday};
+        use super::calc_time::{date_info, get_curr_week_since_this_year, zellers_kongruenz};
+
+        let date: Date = "2024-2-29".parse().unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(date.ordinal(), 60);
+        assert_eq!(date.weekday(), Weekday::Thursday);
+        assert_eq!(date.add_days(366).unwrap().to_string(), "2025-03-01");
+        assert_eq!(date.sub_days(60).unwrap().to_string(), "2023-12-31");
+
+        // 日序号往返
+        assert_eq!(Date::new(1970, 1, 1).unwrap().day_number(), 0);
+        assert_eq!(Date::new(2000, 3, 1).unwrap().day_number(), 11_017);
+        for n in (-800_000..800_000).step_by(997) {
+            let date = Date::from_day_number(n).unwrap();
+            assert_eq!(date.day_number(), n);
+            assert_eq!(Some(date.weekday()), Weekday::from_sunday(zellers_kongruenz(date.year(), date.month(), date.day()).unwrap()));
+        }
+        assert_eq!(Date::new(0, 12, 31).unwrap() - Date::new(-1, 1, 1).unwrap(), 365 + 366 - 1);
+
+        // ISO 周历, 包括跨年的情况
+        let iso = |s: &str| {
+            let (year, week, weekday) = s.parse::<Date>().unwrap().iso_week();
+            (year, week, weekday.number_from_monday())
+        };
+        assert_eq!(iso("2005-01-01"), (2004, 53, 6));
+        assert_eq!(iso("2007-12-31"), (2008, 1, 1));
+        assert_eq!(iso("2010-01-03"), (2009, 53, 7));
+        assert_eq!(iso("2020-12-31"), (2020, 53, 4));
+        assert_eq!(iso("2021-01-04"), (2021, 1, 1));
+        assert_eq!((iso_weeks_in_year(2015), iso_weeks_in_year(2016), iso_weeks_in_year(2020)), (53, 52, 53));
+        assert_eq!(get_curr_week_since_this_year(2005, 1, 1), Ok(53));
+        assert_eq!(get_curr_week_since_this_year(2024, 11, 18), Ok(47));
+        assert_eq!(get_curr_week_since_this_year(2023, 2, 29), Err(DateError::InvalidDay { year: 2023, month: 2, day: 29 }));
+
+        // 类型化的错误
+        assert_eq!("2023-2-29".parse::<Date>(), Err(DateError::InvalidDay { year: 2023, month: 2, day: 29 }));
+        assert_eq!("2023-13-1".parse::<Date>(), Err(DateError::InvalidMonth(13)));
+        assert_eq!("2023-1".parse::<Date>(), Err(DateError::Format("2023-1".to_string())));
+        assert_eq!("2023-x-1".parse::<Date>(), Err(DateError::InvalidNumber("x".to_string())));
+        assert_eq!("99999999-1-1".parse::<Date>(), Err(DateError::YearOutOfRange(99_999_999)));
+        assert_eq!("-44-3-15".parse::<Date>().unwrap().year(), -44);
+        assert_eq!(Date::new(999_999, 12, 31).unwrap().add_days(1), Err(DateError::Overflow));
+
+        // 春节当天距下一个春节的天数, 以及超出农历数据范围的年份
+        assert_eq!(date_info("2024-2-10".parse().unwrap()).unwrap(), "6,325,353");
+        assert_eq!(date_info("2100-1-1".parse().unwrap()), Err(DateError::YearOutOfRange(2100)));
+    }
+
+    #[test]
+    fn test_lunar_calendar() {
+        use super::calc_time::Date;
+        use super::calc_time::lunar_cal::{get_lunar_new_year_date, leap_month, year_days, LunarDate, LunarError};
+        let solar = |s: &str| s.parse::<Date>().unwrap();
+
+        // 与原有的春节数据一致
+        for year in 1901..=2099 {
+            let new_year = LunarDate::new(year, 1, false, 1).unwrap();
+            assert_eq!(new_year.to_solar(), get_lunar_new_year_date(year).unwrap(), "{year}");
+            let next = LunarDate::new(year + 1, 1, false, 1).unwrap();
+            assert_eq!(next.to_solar() - new_year.to_solar(), year_days(year).unwrap() as i64);
+        }
+
+        let lunar = LunarDate::from_solar(solar("2025-1-7")).unwrap();
+        assert_eq!(lunar.to_string(), "甲辰年 腊月 初八");
+        assert_eq!((lunar.zodiac(), lunar.month_ganzhi().to_string()), ("龙", "丁丑".to_string()));
+        let lunar = LunarDate::from_solar(solar("2025-7-25")).unwrap();
+        assert_eq!(lunar.to_string(), "乙巳年 闰六月 初一");
+        assert_eq!((lunar.year(), lunar.month(), lunar.is_leap_month(), lunar.day()), (2025, 6, true, 1));
+        assert_eq!(lunar.to_solar(), solar("2025-7-25"));
+        assert_eq!(lunar.month_ganzhi().to_string(), "癸未");
+        assert_eq!(LunarDate::from_solar(solar("2024-2-10")).unwrap().month_ganzhi().to_string(), "丙寅");
+        assert_eq!(LunarDate::from_solar(solar("2000-1-1")).unwrap().day_ganzhi().to_string(), "戊午");
+        assert_eq!(LunarDate::from_solar(solar("2023-12-22")).unwrap().day_name(), "初十");
+        assert_eq!(LunarDate::from_solar(solar("2024-9-2")).unwrap().to_string(), "甲辰年 七月 三十");
+        assert_eq!(LunarDate::from_solar(solar("2024-1-3")).unwrap().to_string(), "癸卯年 冬月 廿二");
+        assert_eq!(LunarDate::from_solar(solar("1900-1-31")).unwrap().to_string(), "庚子年 正月 初一");
+        assert_eq!((leap_month(2023), leap_month(2024), leap_month(2025)), (Some(2), None, Some(6)));
+
+        // 覆盖范围的两端
+        assert_eq!(LunarDate::from_solar(solar("2101-1-28")).unwrap().to_string(), "庚申年 腊月 廿九");
+        assert_eq!(LunarDate::from_solar(solar("2101-1-29")), Err(LunarError::DateOutOfRange(solar("2101-1-29"))));
+        assert_eq!(LunarDate::from_solar(solar("1900-1-30")), Err(LunarError::DateOutOfRange(solar("1900-1-30"))));
+
+        // 往返转换
+        let mut date = solar("1900-1-31");
+        while date <= solar("2101-1-28") {
+            assert_eq!(LunarDate::from_solar(date).unwrap().to_solar(), date);
+            date = date.add_days(13).unwrap();
+        }
+
+        assert_eq!(LunarDate::new(2024, 6, true, 1), Err(LunarError::NoLeapMonth { year: 2024, month: 6 }));
+        assert_eq!(LunarDate::new(2024, 1, false, 30), Err(LunarError::InvalidDay { year: 2024, month: 1, leap: false, day: 30 }));
+        assert_eq!(LunarDate::new(2101, 1, false, 1), Err(LunarError::YearOutOfRange(2101)));
+    }
+
+    #[test]
+    fn test_solar_terms_and_festivals() {
+        use super::calc_time::{date_info, festival_info, Date};
+        use super::calc_time::festival::{upcoming, Festival};
+        use super::calc_time::lunar_cal::LunarError;
+        use super::calc_time::solar_term::{solar_term_date, solar_term_of, solar_terms, SolarTermError};
+        let solar = |s: &str| s.parse::<Date>().unwrap();
+
+        // 2024 年的节气 (北京时间)
+        let days = [6, 20, 4, 19, 5, 20, 4, 19, 5, 20, 5, 21, 6, 22, 7, 22, 7, 22, 8, 23, 7, 22, 6, 21];
+        for (idx, (name, date)) in solar_terms(2024).unwrap().into_iter().enumerate() {
+            assert_eq!(date, Date::new(2024, idx as u32 / 2 + 1, days[idx]).unwrap(), "{name}");
+            assert_eq!(solar_term_of(date), Ok(Some(name)));
+        }
+        assert_eq!(solar_term_of(solar("2024-12-20")), Ok(None));
+        // 覆盖范围的两端, 超出范围时返回错误
+        assert_eq!(solar_terms(1900).unwrap()[5].1, solar("1900-3-21"));
+        assert_eq!(solar_terms(2100).unwrap()[23].1, solar("2100-12-22"));
+        assert_eq!(solar_terms(2101), Err(SolarTermError::YearOutOfRange(2101)));
+        assert_eq!(solar_term_of(solar("1899-12-22")), Err(SolarTermError::YearOutOfRange(1899)));
+        assert_eq!(solar_term_date(2024, 24), Err(SolarTermError::InvalidIndex(24)));
+
+        assert_eq!(Festival::MidAutumn.date_in(2025), Ok(solar("2025-10-6")));
+        assert_eq!(Festival::DragonBoat.date_in(2025), Ok(solar("2025-5-31")));
+        assert_eq!(Festival::Qingming.date_in(2025), Ok(solar("2025-4-4")));
+        assert_eq!(Festival::DoubleNinth.date_in(2025), Ok(solar("2025-10-29")));
+        assert_eq!(Festival::LunarNewYear.next_after(solar("2025-1-29")), Ok(solar("2026-2-17")));
+        assert_eq!(Festival::MidAutumn.days_until(solar("2025-10-5")), Ok(0));
+        assert_eq!(Festival::MidAutumn.days_until(solar("2025-10-6")), Ok(353));
+
+        let next: Vec<Festival> = upcoming(solar("2025-10-1")).unwrap().into_iter().map(|(f, _, _)| f).collect();
+        assert_eq!(next, [Festival::MidAutumn, Festival::DoubleNinth, Festival::LunarNewYear, Festival::Qingming, Festival::DragonBoat]);
+        assert_eq!(festival_info(solar("2025-10-1")), Ok("中秋:4,重阳:27,春节:138,清明:185,端午:260".to_string()));
+        // 与 date_info 中距春节的天数一致
+        assert_eq!(date_info(solar("2025-10-1")).unwrap().rsplit(',').next(), Some("138"));
+        assert_eq!(Festival::Qingming.date_in(2101), Err(LunarError::YearOutOfRange(2101)));
+        assert_eq!(Festival::DragonBoat.next_after(solar("2100-7-1")), Err(LunarError::YearOutOfRange(2101)));
+    }
+
+    #[test]
+    fn test_business_days() {
+        use super::calc_time::Date;
+        use super::calc_time::business_day::{CalendarError, HolidayCalendar};
+        let solar = |s: &str| s.parse::<Date>().unwrap();
+        let calendar = HolidayCalendar::load(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("holidays.txt")).unwrap();
+
+        assert!(calendar.is_working_day(solar("2025-1-26")));     // 周日调休上班
+        assert!(!calendar.is_working_day(solar("2025-2-3")));     // 周一春节
+        assert!(!calendar.is_working_day(solar("2025-3-1")));     // 普通周六
+        assert!(calendar.is_working_day(solar("2025-3-3")));
+        assert_eq!(calendar.holiday(solar("2025-10-8")), Some("国庆、中秋"));
+
+        assert_eq!(calendar.add_working_days(solar("2025-9-30"), 3), Ok(solar("2025-10-11")));
+        assert_eq!(calendar.add_working_days(solar("2025-10-9"), -1), Ok(solar("2025-9-30")));
+        assert_eq!(calendar.add_working_days(solar("2025-10-1"), 0), Ok(solar("2025-10-1")));
+
+        assert_eq!(calendar.working_days_between(solar("2024-1-1"), solar("2025-1-1")), 251);
+        assert_eq!(calendar.working_days_between(solar("2025-1-1"), solar("2026-1-1")), 248);
+        assert_eq!(calendar.working_days_between(solar("2026-1-1"), solar("2025-1-1")), -248);
+        // 与逐天计数一致
+        let start = solar("2023-12-20");
+        for len in 0..500 {
+            let end = start.add_days(len).unwrap();
+            let brute = (0..len).filter(|&i| calendar.is_working_day(start.add_days(i).unwrap())).count() as i64;
+            assert_eq!(calendar.working_days_between(start, end), brute, "{end}");
+            if brute > 0 {
+                let last = calendar.add_working_days(start.sub_days(1).unwrap(), brute).unwrap();
+                assert!(last < end && calendar.is_working_day(last));
+            }
+        }
+
+        let parse = |s: &str| HolidayCalendar::parse(s.as_bytes());
+        assert!(matches!(parse("休 2025-1-1\n班 2025-1-1"), Err(CalendarError::Conflict { line: 2, .. })));
+        assert!(matches!(parse("放假 2025-1-1"), Err(CalendarError::Syntax { line: 1, .. })));
+        assert!(matches!(parse("\n# 注释\n休"), Err(CalendarError::Syntax { line: 3, .. })));
+        assert!(matches!(parse("休 2025-2-30"), Err(CalendarError::Date { line: 1, .. })));
+        assert!(matches!(parse("holiday 2025-2-3..2025-2-1"), Err(CalendarError::Syntax { line: 1, .. })));
+    }
+
+    #[test]
+    fn test_julian_calendar() {
+        use super::calc_time::{Date, DateError};
+        use super::calc_time::date::Weekday;
+        use super::calc_time::julian::{Calendar, Changeover, HistoricalDate, JulianDate};
+        let solar = |s: &str| s.parse::<Date>().unwrap();
+        let julian = |s: &str| s.parse::<JulianDate>().unwrap();
+
+        assert_eq!(solar("2000-1-1").jdn(), 2_451_545);
+        assert_eq!(julian("-4712-1-1").jdn(), 0);
+        assert_eq!(Date::from_jdn(0), Ok(solar("-4713-11-24")));
+        assert_eq!(julian("1582-10-4").jdn() + 1, solar("1582-10-15").jdn());
+        assert_eq!(julian("1918-1-31").to_gregorian(), Ok(solar("1918-2-13")));
+        assert_eq!(JulianDate::from_gregorian(solar("2024-1-1")), Ok(julian("2023-12-19")));
+        assert_eq!((julian("1582-10-4").weekday(), solar("1582-10-15").weekday()), (Weekday::Thursday, Weekday::Friday));
+
+        // 儒略历中整百年与 0 年都是闰年
+        assert!(JulianDate::new(1900, 2, 29).is_ok());
+        assert_eq!(julian("0-2-29").to_string(), "0000-02-29");
+        assert_eq!(Date::new(1900, 2, 29), Err(DateError::InvalidDay { year: 1900, month: 2, day: 29 }));
+        assert_eq!(JulianDate::from_jdn(i64::MAX), Err(DateError::Overflow));
+
+        for jdn in (-2_000_000..4_000_000).step_by(997) {
+            let date = JulianDate::from_jdn(jdn).unwrap();
+            assert_eq!(date.jdn(), jdn);
+            assert_eq!(date.to_string().parse::<JulianDate>(), Ok(date));
+            assert_eq!(Date::from_jdn(jdn).unwrap().jdn(), jdn);
+        }
+
+        let gregory = Changeover::default();
+        assert_eq!(gregory.last_julian(), Ok(julian("1582-10-4")));
+        assert_eq!(gregory.parse("1582-10-4"), Ok(HistoricalDate::Julian(julian("1582-10-4"))));
+        assert_eq!(gregory.parse("1582-10-10"), Err(DateError::InvalidDay { year: 1582, month: 10, day: 10 }));
+        assert_eq!(gregory.parse("1500-2-29").map(|d| d.calendar()), Ok(Calendar::Julian));
+        let next = gregory.date_at(julian("1582-10-4").jdn() + 1).unwrap();
+        assert_eq!((next.calendar(), next.ymd()), (Calendar::Gregorian, (1582, 10, 15)));
+
+        let britain = Changeover::new(solar("1752-9-14"));
+        assert_eq!(britain.first_gregorian(), solar("1752-9-14"));
+        assert_eq!(britain.last_julian(), Ok(julian("1752-9-2")));
+        assert_eq!(britain.parse("1700-2-29").unwrap().to_string(), "1700-02-29");
+        assert!(britain.parse("1752-9-10").is_err());
+        assert_eq!(britain.parse("1752-9-14").unwrap().jdn() - britain.parse("1752-9-2").unwrap().jdn(), 1);
+    }
 }
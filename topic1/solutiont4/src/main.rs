@@ -1,6 +1,43 @@
 mod wade_giles_romanization;
 
+use wade_giles_romanization::name::Format;
+use wade_giles_romanization::scheme::{self, PinyinToneMarks, Romanization, SCHEMES};
+
 fn main() {
     let res = wade_giles_romanization::converter("澳門");
     println!("{res}");
+
+    for scheme in SCHEMES {
+        println!("{}: {}", scheme.name(), wade_giles_romanization::romanize("中华民国", scheme));
+    }
+
+    let (from, to) = (scheme::find_scheme("wade-giles").unwrap(), scheme::find_scheme("zhuyin").unwrap());
+    match scheme::convert("Mao² Tsê²-tung¹", from, to) {
+        Ok(text) => println!("{text}"),
+        Err(e) => println!("{e}"),
+    }
+
+    // 命令行参数给出数据目录时, 使用该目录下的数据文件代替编译时嵌入的数据
+    let loaded = std::env::args_os().nth(1).map(wade_giles_romanization::Converter::load);
+    let converter = match &loaded {
+        Some(Ok(converter)) => converter,
+        Some(Err(e)) => {
+            println!("failed to load dictionaries: {e}");
+            wade_giles_romanization::Converter::shared()
+        }
+        None => wade_giles_romanization::Converter::shared(),
+    };
+    println!("{} phrases: {}", converter.phrases().len(), converter.wade_giles("南京市长江大桥"));
+    for (c, readings) in wade_giles_romanization::candidate_readings("银行行长的长处") {
+        let pinyin: Vec<String> = readings.iter().map(|syllable| PinyinToneMarks.encode(syllable)).collect();
+        println!("{c}: {}", pinyin.join("/"));
+    }
+
+    println!("{}", wade_giles_romanization::convert("诸葛亮、欧阳修", Format::PersonalName));
+    println!("{}", wade_giles_romanization::convert("北京、重庆", Format::PlaceName));
+    println!("{}", wade_giles_romanization::convert("我住在北京，电话是12345。", Format::Text));
+
+    for candidate in wade_giles_romanization::hanzi_candidates("Mao Tse-tung", 5) {
+        println!("{}", candidate.hanzi);
+    }
 }
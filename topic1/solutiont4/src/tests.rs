@@ -4,6 +4,11 @@ mod wade_giles_romanization;
 #[cfg(test)]
 mod tests {
     use super::wade_giles_romanization::converter;
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::io;
+    use std::io::{BufRead, BufReader};
+    use std::path::Path;
     use std::time::{Instant, Duration};
 
     // 定义测试用例和预期结果
@@ -37,4 +42,198 @@ mod tests {
         println!("Total score: {:.2}", total_score);
         assert_eq!(100.00, total_score);
     }
+
+    #[test]
+    fn test_romanization_schemes() {
+        use super::wade_giles_romanization::romanize;
+        use super::wade_giles_romanization::scheme::*;
+        use super::wade_giles_romanization::syllable::Syllable;
+
+        let syllable = |pinyin: &str| Syllable::from_pinyin(pinyin).unwrap();
+        let spell = |pinyin: &str| -> Vec<String> {
+            SCHEMES.iter().map(|scheme| scheme.encode(&syllable(pinyin))).collect()
+        };
+        assert_eq!(spell("zhong1"), ["zhōng", "zhong1", "chung¹", "jong", "jūng", "ㄓㄨㄥ"]);
+        assert_eq!(spell("qian2"), ["qián", "qian2", "ch'ien²", "chyan", "chyán", "ㄑㄧㄢˊ"]);
+        assert_eq!(spell("shui3"), ["shuǐ", "shui3", "shui³", "shoei", "shwěi", "ㄕㄨㄟˇ"]);
+        assert_eq!(spell("lü4"), ["lǜ", "lv4", "lü⁴", "liuh", "lyù", "ㄌㄩˋ"]);
+        assert_eq!(spell("si4"), ["sì", "si4", "ssu⁴", "syh", "sz̀", "ㄙˋ"]);
+        assert_eq!(spell("ma1")[3], "mha");
+        assert_eq!(spell("you3")[3], "yeou");
+        assert_eq!(spell("men5")[5], "˙ㄇㄣ");
+
+        // 与威妥玛对照表一致 (对照表不使用 ê, 且有两处笔误)
+        let mapping = load_mapping(concat!(env!("CARGO_MANIFEST_DIR"), "/mapping_pingyin_to_wade_giles")).unwrap();
+        for (pinyin, expected) in mapping.iter().filter(|(pinyin, _)| !["tie", "luan"].contains(&pinyin.as_str())) {
+            let pinyin = pinyin.replace("lue", "lüe").replace("nue", "nüe");
+            assert_eq!(WadeGiles.encode(&syllable(&pinyin)).replace('ê', "e"), *expected, "{pinyin}");
+        }
+
+        // 每种方案都能解析回自己拼写的全部音节
+        for scheme in SCHEMES {
+            for tone in [Some(1), Some(2), Some(3), Some(4)] {
+                for syllable in Syllable::all().map(|syllable| syllable.with_tone(tone)) {
+                    let text = scheme.encode(&syllable);
+                    assert!(scheme.decode_all(&text).contains(&syllable), "{}: {text}", scheme.name());
+                }
+            }
+        }
+        // 同一拼写对应多个音节时常用的在前
+        assert_eq!(WadeGiles.decode("lo").map(|syllable| syllable.pinyin()), Some("luo".to_string()));
+        assert_eq!(Zhuyin.decode("ㄓㄨㄥ").and_then(|syllable| syllable.tone()), Some(1));
+
+        let wade_giles = find_scheme("Wade-Giles").unwrap();
+        assert_eq!(convert("Mao² Tsê²-tung¹", wade_giles, &PinyinToneMarks), Ok("Máo Zé-dōng".to_string()));
+        assert_eq!(convert("Mao2 Tse2-tung1", wade_giles, &GwoyeuRomatzyh), Ok("Mau Tzer-dong".to_string()));
+        assert_eq!(convert("Hsiang kang", wade_giles, &PinyinToneNumbers), Ok("Xiang gang".to_string()));
+        assert_eq!(convert("xi'an", &PinyinToneMarks, &Yale), Ok("syi'an".to_string()));
+        assert_eq!(convert("ㄅㄟˇ ㄐㄧㄥ", &Zhuyin, &PinyinToneMarks), Ok("běi jīng".to_string()));
+        assert_eq!(convert("Peking", wade_giles, &Yale), Err(RomanizationError::UnknownSyllable {
+            scheme: "wade-giles",
+            text: "Peking".to_string(),
+        }));
+
+        assert_eq!(romanize("台湾", &WadeGiles), "t'ai² wan¹");
+        assert_eq!(romanize("北京!", &Zhuyin), "ㄅㄟˇ ㄐㄧㄥ !");
+    }
+
+    #[test]
+    fn test_polyphones() {
+        use super::wade_giles_romanization::{candidate_readings, converter, romanize};
+        use super::wade_giles_romanization::phrase::PhraseDict;
+        use super::wade_giles_romanization::scheme::{PinyinToneMarks, Romanization};
+
+        assert_eq!(romanize("重庆", &PinyinToneMarks), "chóng qìng");
+        assert_eq!(romanize("长安", &PinyinToneMarks), "cháng ān");
+        assert_eq!(romanize("银行行长", &PinyinToneMarks), "yín háng háng zhǎng");
+        assert_eq!(romanize("南京市长江大桥", &PinyinToneMarks), "nán jīng shì cháng jiāng dà qiáo");
+        assert_eq!(converter("重庆"), "Ch'ung ch'ing");
+        assert_eq!(converter("厦门"), "Hsia men");
+
+        // 不在词语中的多音字保留全部候选, 最常用的读音在前
+        let readings = candidate_readings("长");
+        let pinyin: Vec<String> = readings[0].1.iter().map(|syllable| PinyinToneMarks.encode(syllable)).collect();
+        assert_eq!(pinyin, ["zhǎng", "cháng"]);
+        let readings = candidate_readings("长江@");
+        assert_eq!(readings.iter().map(|(_, candidates)| candidates.len()).collect::<Vec<usize>>(), [1, 1, 0]);
+
+        let dict = PhraseDict::parse("# 注释\nAB: a bā\nBC: bō cí\nCDE: cì dé è".as_bytes()).unwrap();
+        let chars: Vec<char> = "ABCDE".chars().collect();
+        assert_eq!(dict.segment(&chars), [2, 3]);
+        assert_eq!(dict.segment(&chars[..3]), [2, 1]);
+        assert!(PhraseDict::parse("AB: a".as_bytes()).is_err());
+        assert!(PhraseDict::parse("AB a ba".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_shared_converter() {
+        use super::wade_giles_romanization::Converter;
+
+        let shared = Converter::shared();
+        assert!(std::ptr::eq(shared, Converter::shared()));
+        assert_eq!(shared.readings('长').len(), 2);
+        assert!(shared.readings('@').is_empty());
+
+        let loaded = Converter::load(env!("CARGO_MANIFEST_DIR")).unwrap();
+        assert_eq!(loaded.phrases().len(), shared.phrases().len());
+        assert_eq!(loaded.wade_giles("诸葛亮"), shared.wade_giles("诸葛亮"));
+        assert!(Converter::load("/nonexistent").is_err());
+
+        let document = "中华人民共和国香港特别行政区".repeat(200);
+        let res = shared.wade_giles(&document);
+        assert!(res.starts_with("Chung hua jen min kung ho kuo hsiang kang"));
+    }
+
+    #[test]
+    fn test_name_formats() {
+        use super::wade_giles_romanization::{convert, Converter};
+        use super::wade_giles_romanization::name::{split_surname, Format};
+
+        assert_eq!(split_surname("诸葛亮"), ("诸葛", "亮"));
+        assert_eq!(split_surname("欧阳修"), ("欧阳", "修"));
+        assert_eq!(split_surname("毛泽东"), ("毛", "泽东"));
+        assert_eq!(split_surname("诸"), ("诸", ""));
+        assert_eq!(split_surname(""), ("", ""));
+
+        let converter = Converter::shared();
+        assert_eq!(converter.personal_name("毛泽东"), "Mao Tse-tung");
+        assert_eq!(converter.personal_name("诸葛亮"), "Chu-ko Liang");
+        assert_eq!(converter.personal_name("欧阳修"), "Ou-yang Hsiu");
+        assert_eq!(converter.personal_name("蒋介石"), "Chiang Chieh-shih");
+        assert_eq!(converter.personal_name("曾国藩"), "Tseng Kuo-fan");
+        assert_eq!(converter.personal_name("单雄信"), "Shan Hsiung-hsin");
+        assert_eq!(converter.place_name("北京"), "Peiching");
+        assert_eq!(converter.place_name("重庆"), "Ch'ungch'ing");
+
+        assert_eq!(convert("毛泽东、周恩来", Format::PersonalName), "Mao Tse-tung, Chou En-lai");
+        assert_eq!(convert("台湾 (Taiwan)", Format::PlaceName), "T'aiwan (Taiwan)");
+        assert_eq!(convert("我有3个iPhone，你呢？好。", Format::Text), "Wo yu 3 ko iPhone, ni ne? Hao.");
+        assert_eq!(convert("ＡＢＣ－123", Format::Text), "ABC-123");
+        assert_eq!(convert("", Format::Text), "");
+    }
+
+    #[test]
+    fn test_reverse_conversion() {
+        use super::wade_giles_romanization::hanzi_candidates;
+        use super::wade_giles_romanization::reverse::{decode_syllable, segmentations};
+        use super::wade_giles_romanization::syllable::Syllable;
+
+        let syllable = |pinyin: &str| Syllable::from_pinyin(pinyin).unwrap();
+        assert_eq!(decode_syllable("hsi"), [syllable("xi")]);
+        assert_eq!(decode_syllable("Shi"), [syllable("shi"), syllable("xi")]);
+        assert_eq!(decode_syllable("ch'ing²"), [syllable("qing2")]);
+        assert_eq!(decode_syllable("chung"), [syllable("zhong"), syllable("chong")]);
+        assert_eq!(decode_syllable("Sze"), [syllable("si")]);
+        assert_eq!(decode_syllable("zhōng"), [syllable("zhong1")]);
+        assert!(decode_syllable("xyz").is_empty());
+
+        let texts = |input: &str| -> Vec<Vec<String>> {
+            segmentations(input).into_iter()
+                .map(|pieces| pieces.into_iter().map(|piece| piece.text).collect())
+                .collect()
+        };
+        assert_eq!(texts("xian"), [vec!["xian"], vec!["xi", "an"]]);
+        assert_eq!(texts("xi'an"), [vec!["xi", "an"]]);
+        assert_eq!(texts("Ch'ung-ch'ing"), [vec!["Ch'ung", "ch'ing"]]);
+        assert_eq!(texts("Peiching")[0], ["Pei", "ching"]);
+        assert!(texts("@@").is_empty());
+
+        let first = |input: &str| hanzi_candidates(input, 10).into_iter().next().map(|candidate| candidate.hanzi);
+        assert_eq!(first("Ch'ung-ch'ing").as_deref(), Some("重庆"));
+        assert_eq!(first("Chungking").as_deref(), Some("重庆"));
+        assert_eq!(first("chung1 kuo2").as_deref(), Some("中国"));
+        assert_eq!(first("zhōng guó").as_deref(), Some("中国"));
+        assert_eq!(first("xi'an").as_deref(), Some("西安"));
+        assert_eq!(first("Hsi").as_deref(), Some("西"));
+        assert_eq!(first("@@"), None);
+
+        let candidates = hanzi_candidates("Nanching", 10);
+        assert_eq!(candidates.len(), 10);
+        assert!(candidates.iter().any(|candidate| candidate.hanzi == "南京"));
+        assert_eq!(candidates[0].syllables.len(), 2);
+        assert!(hanzi_candidates("Mao Tse-tung", 20).iter().any(|candidate| candidate.hanzi == "毛泽东"));
+    }
+
+    /// 加载 path 处的对照表文件
+    /// 并根据文件内部的组织规则, 将每行中的一一映射转换成为对应的 HashMap 映射
+    fn load_mapping(path: impl AsRef<Path>) -> io::Result<HashMap<String, String>> {
+        let mut map = HashMap::new();
+
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+        for line in reader.lines() {
+            let line = line?;
+            if line.starts_with("#") {
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let modify = parts[0].replace("`", "'").to_string();
+            if parts.len() >= 2 {
+                map.insert(parts[1].to_string(), modify);
+            }
+        }
+
+        Ok(map)
+    }
 }
@@ -1,115 +1,294 @@
-use std::collections::HashMap;
+pub mod name;
+pub mod phrase;
+pub mod reverse;
+pub mod scheme;
+pub mod syllable;
+
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io;
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
-use unicode_normalization::UnicodeNormalization;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::sync::OnceLock;
+use name::Format;
+use phrase::PhraseDict;
+use reverse::Candidate;
+use scheme::{Romanization, WadeGiles};
+use syllable::Syllable;
 
-// TODO: 可以将对应威妥玛拼音的映射关系与前面字形到拼音的转换表结合在一起,然后通过写入到某个特定的文件,用时再读取的方式降低重复构建的性能损耗
-pub fn converter(input: &str) -> String {
-    dbg!(&input);
-    let kMandarin_map = load_pin_yin_file("kMandarin.txt".into()).unwrap();
-    let mapping = load_mapping("mapping_pingyin_to_wade_giles".into()).unwrap();
-    // dbg!(&kMandarin_map);
-
-    // 首先基于基础字形映射 将对应的中文内容转换成为对应的 拼音
-    let pingyin = input.chars()
-        // .map(|c| kMandarin_map[&c].clone())
-        .map(|c| kMandarin_map.get(&c).unwrap_or(&" ".into()).clone())
-        .collect::<Vec<_>>();
-    // dbg!(&pingyin);
-
-    if pingyin.len() <= 1 && pingyin[0] == " " {
-        return "".into()
-    }
-
-    // 然后再根据拼音与威妥玛拼音之间的映射关系, 将代表对应字符的拼音转换成为对应的威妥玛拼音
-    // ("诸葛亮", "Chu ko liang"),
-    // stage 1: zhu ge liang
-    let mut res: Vec<_> = pingyin
-        .iter()
-        .map(|words| mapping.get(words).unwrap_or(&" ".into()).clone())
-        .collect();
-
-    // 根据答案的要求,将字符串的第一个 ASCII 转换成为大写表达形式
-    if let Some(first) = res.first_mut() {
-        let mut chars = first.chars();
-        match chars.next() {
-            None => (),
-            Some(c) => {
-                *first = std::iter::once(c.to_uppercase().next().unwrap())
-                    .chain(chars)
-                    .collect()
+/// 编译时嵌入的读音数据, 运行时不再依赖 CARGO_MANIFEST_DIR 下的数据文件
+const KMANDARIN: &str = include_str!("../kMandarin.txt");
+const KMANDARIN_8105: &str = include_str!("../kMandarin_8105.txt");
+const PHRASE_PINYIN: &str = include_str!("../phrase_pinyin.txt");
+const HANZI_FREQUENCY: &str = include_str!("../hanzi_frequency.txt");
+
+/// 汉字到音节的转换器, 持有字的读音表与词语词典
+///
+/// # 主要特点:
+/// - 词典只在构建时解析一次, 之后的每次转换只做查表与分词
+/// - `Converter::shared()` 返回进程内共享的实例, 首次使用时由嵌入的数据构建
+/// - 也可以通过 `Converter::load` 从其他目录中的同名数据文件构建
+/// - 反查汉字用的音节索引在第一次反查时构建
+#[derive(Debug, Clone)]
+pub struct Converter {
+    readings: HashMap<char, Vec<Syllable>>,
+    phrases: PhraseDict,
+    /// 《通用规范汉字表》收录的字
+    common: HashSet<char>,
+    /// 常用字的字频排名
+    frequency: HashMap<char, usize>,
+    index: OnceLock<HashMap<Syllable, Vec<(char, Syllable)>>>,
+}
+
+impl Converter {
+    /// 由编译时嵌入的数据构建
+    pub fn new() -> Self {
+        Self::parse(KMANDARIN.as_bytes(), KMANDARIN_8105.as_bytes(), PHRASE_PINYIN.as_bytes(), HANZI_FREQUENCY.as_bytes())
+            .expect("embedded dictionaries are well-formed")
+    }
+
+    /// 从 dir 目录下的 kMandarin.txt、kMandarin_8105.txt、phrase_pinyin.txt 与 hanzi_frequency.txt 构建
+    pub fn load(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        Self::parse(
+            File::open(dir.join("kMandarin.txt"))?,
+            File::open(dir.join("kMandarin_8105.txt"))?,
+            File::open(dir.join("phrase_pinyin.txt"))?,
+            File::open(dir.join("hanzi_frequency.txt"))?,
+        )
+    }
+
+    /// 合并字的读音 (kMandarin, 并补充 kMandarin_8105 中标注的其他读音) 与词语读音,
+    /// 词语中出现过的读音也作为该字的候选; frequency 为反查汉字时使用的字频表
+    pub fn parse(kmandarin: impl Read, kmandarin_8105: impl Read, phrases: impl Read, frequency: impl Read) -> io::Result<Self> {
+        let mut readings = parse_readings(kmandarin)?;
+        let extra = parse_readings(kmandarin_8105)?;
+        let phrases = PhraseDict::parse(phrases)?;
+        let common = extra.keys().copied().collect();
+        let frequency = reverse::parse_frequency(frequency)?;
+
+        let extra = extra.into_iter().flat_map(|(c, syllables)| syllables.into_iter().map(move |syllable| (c, syllable)));
+        for (c, syllable) in extra.chain(phrases.char_readings()) {
+            let entry = readings.entry(c).or_default();
+            if !entry.contains(&syllable) {
+                entry.push(syllable);
             }
         }
+        Ok(Converter { readings, phrases, common, frequency, index: OnceLock::new() })
     }
 
-    res.join(" ")
-}
+    /// 进程内共享的实例
+    pub fn shared() -> &'static Converter {
+        static SHARED: OnceLock<Converter> = OnceLock::new();
+        SHARED.get_or_init(Converter::new)
+    }
 
-/// 从基于 CARGO_MANIFEST_DIR 的相对路径中加载 file_name 的文件
-/// 并根据文件内部的组织规则, 将每行中的一一映射转换成为对应的 HashMap 映射
-pub fn load_mapping<'a>(file_name: String) -> io::Result<HashMap<String, String>> {
-    let manifest_path = std::env::var("CARGO_MANIFEST_DIR").unwrap();
-    let path = PathBuf::from(manifest_path).join(file_name);
-    let mut map = HashMap::new();
+    pub fn readings(&self, c: char) -> &[Syllable] {
+        self.readings.get(&c).map_or(&[], Vec::as_slice)
+    }
 
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    for line in reader.lines() {
-        let line = line?;
-        if line.starts_with("#") {
-            continue;
+    pub fn phrases(&self) -> &PhraseDict {
+        &self.phrases
+    }
+
+    /// input 中每个字的候选读音, 按词语确定了读音的字只有一个候选, 其余的字第一个候选为最常用的读音,
+    /// 不认识的字符没有候选
+    pub fn candidate_readings(&self, input: &str) -> Vec<(char, Vec<Syllable>)> {
+        self.phrases.readings(input, &self.readings)
+    }
+
+    /// 转换为首字母大写、以空格分隔的不带声调的威妥玛拼音, 不认识的字符转换为空格
+    pub fn wade_giles(&self, input: &str) -> String {
+        // 首先基于字形与词语的映射 将对应的中文内容转换成为对应的音节, 再拼写为不带声调的威妥玛拼音
+        // ("诸葛亮", "Chu ko liang"),
+        // stage 1: zhu ge liang
+        // 原有的输出不使用 ê 的附加符号, 如 "Ao men"
+        let mut res: Vec<String> = self.candidate_readings(input)
+            .into_iter()
+            .map(|(_, syllables)| match syllables.first() {
+                Some(syllable) => plain_wade_giles(syllable),
+                None => " ".into(),
+            })
+            .collect();
+
+        if res.len() <= 1 && res.first().is_none_or(|first| first == " ") {
+            return "".into()
         }
 
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        let modify = parts[0].replace("`", "'").to_string();
-        if parts.len() >= 2 {
-            map.insert(parts[1].to_string(), modify);
+        // 根据答案的要求,将字符串的第一个 ASCII 转换成为大写表达形式
+        if let Some(first) = res.first_mut() {
+            *first = scheme::capitalize(first);
         }
+
+        res.join(" ")
     }
 
-    Ok(map)
+    /// 将 input 中的汉字按词语确定读音后逐字转换为 scheme 的拼写 (带声调), 以空格分隔, 不认识的字符原样保留
+    pub fn romanize(&self, input: &str, scheme: &dyn Romanization) -> String {
+        self.candidate_readings(input)
+            .into_iter()
+            .map(|(c, syllables)| match syllables.first() {
+                Some(syllable) => scheme.encode(syllable),
+                None => c.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+    /// 人名格式, 如 "毛泽东" 为 "Mao Tse-tung", "诸葛亮" 为 "Chu-ko Liang"
+    pub fn personal_name(&self, name: &str) -> String {
+        let (surname, given) = name::split_surname(name);
+        let surname = match name::surname_reading(surname) {
+            Some(syllables) => syllables.iter().map(plain_wade_giles).collect(),
+            None => self.spell(surname),
+        };
+        [surname, self.spell(given)].iter()
+            .filter(|syllables| !syllables.is_empty())
+            .map(|syllables| scheme::capitalize(&syllables.join("-")))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// 地名格式, 如 "北京" 为 "Peiching"
+    pub fn place_name(&self, name: &str) -> String {
+        scheme::capitalize(&self.spell(name).concat())
+    }
+
+    /// 转换中英文混合的文本: 连续的汉字按 format 转换, 拉丁字母、数字与标点原样保留 (全角字符转换为半角),
+    /// 转换结果与相邻的字母、数字之间补充空格
+    pub fn convert(&self, input: &str, format: Format) -> String {
+        let is_hanzi = |c: &char| !self.readings(*c).is_empty();
+        let chars: Vec<char> = input.chars().collect();
+        let mut res = String::new();
+        let mut sentence_start = true;
+        let mut after_hanzi = false;
+        for run in chars.chunk_by(|a, b| is_hanzi(a) == is_hanzi(b)) {
+            if !is_hanzi(&run[0]) {
+                let text: String = run.iter().map(|&c| halfwidth(c)).collect();
+                if after_hanzi && text.starts_with(|c: char| c.is_alphanumeric() || "([{“‘".contains(c)) {
+                    res.push(' ');
+                }
+                if let Some(last) = text.trim_end().chars().last() {
+                    sentence_start = matches!(last, '.' | '!' | '?');
+                }
+                res.push_str(&text);
+                after_hanzi = false;
+                continue;
+            }
+
+            if res.ends_with(|c: char| c.is_alphanumeric() || ",.!?:;)]}%”’".contains(c)) {
+                res.push(' ');
+            }
+            let text: String = run.iter().collect();
+            let words = match format {
+                Format::Text => {
+                    let mut syllables = self.spell(&text);
+                    if let Some(first) = syllables.first_mut().filter(|_| sentence_start) {
+                        *first = scheme::capitalize(first);
+                    }
+                    syllables.join(" ")
+                }
+                Format::PersonalName => self.personal_name(&text),
+                Format::PlaceName => self.place_name(&text),
+            };
+            res.push_str(&words);
+            sentence_start = false;
+            after_hanzi = true;
+        }
+        res
+    }
+
+    /// 每个字的不带声调的威妥玛拼音, 不认识的字符被跳过
+    fn spell(&self, text: &str) -> Vec<String> {
+        self.candidate_readings(text)
+            .into_iter()
+            .filter_map(|(_, syllables)| syllables.first().map(plain_wade_giles))
+            .collect()
+    }
+}
+
+impl Default for Converter {
+    fn default() -> Self {
+        Converter::new()
+    }
+}
+
+/// 使用共享的 Converter 转换为不带声调的威妥玛拼音
+pub fn converter(input: &str) -> String {
+    Converter::shared().wade_giles(input)
+}
+
+/// 使用共享的 Converter 转换为 scheme 的拼写
+pub fn romanize(input: &str, scheme: &dyn Romanization) -> String {
+    Converter::shared().romanize(input, scheme)
 }
 
-/// 从基于 CARGO_MANIFEST_DIR 的相对路径中加载 file_name 的拼音文件
-/// 并根据文件内部的组织规则, 将每行的多个对应关系转换成为对应的 HashMap 映射
-pub fn load_pin_yin_file(file_name: String) -> io::Result<HashMap<char, String>> {
-    let manifest_path = std::env::var("CARGO_MANIFEST_DIR").unwrap();
-    let path = PathBuf::from(manifest_path).join(file_name);
-    let mut map: HashMap<char, String> = HashMap::new();
-    let file = File::open(path)?;
+/// 使用共享的 Converter 给出每个字的候选读音
+pub fn candidate_readings(input: &str) -> Vec<(char, Vec<Syllable>)> {
+    Converter::shared().candidate_readings(input)
+}
+
+/// 使用共享的 Converter 按 format 转换中英文混合的文本
+pub fn convert(input: &str, format: Format) -> String {
+    Converter::shared().convert(input, format)
+}
+
+/// 使用共享的 Converter 由威妥玛拼音或汉语拼音反查汉字
+pub fn hanzi_candidates(input: &str, limit: usize) -> Vec<Candidate> {
+    Converter::shared().hanzi_candidates(input, limit)
+}
+
+/// 不带声调、不使用 ê 附加符号的威妥玛拼写, 与对照表一致
+fn plain_wade_giles(syllable: &Syllable) -> String {
+    WadeGiles.encode(&syllable.with_tone(None)).replace('ê', "e")
+}
 
-    let reader = BufReader::new(file);
-    for line in reader.lines() {
+/// 全角字符与中文句号、顿号转换为对应的半角字符
+fn halfwidth(c: char) -> char {
+    match c {
+        '。' => '.',
+        '、' => ',',
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// 解析 "U+4CA2: téng  # 䲢" 形式的拼音文件, 读音解析为 Syllable, 每个字保留全部读音:
+/// 冒号之后的读音在前, 注释中 "->" 等标注的其他读音在后;
+/// 无法解析的读音 (如 m、ng 等叹词) 被跳过
+pub fn parse_readings(reader: impl Read) -> io::Result<HashMap<char, Vec<Syllable>>> {
+    let mut map: HashMap<char, Vec<Syllable>> = HashMap::new();
+
+    for line in BufReader::new(reader).lines() {
         let line = line?;
         if line.starts_with("#") {
             continue
         }
-        let line = line.split_whitespace().collect::<Vec<_>>();
-        // U+4CA2: téng  # 䲢
-        // U+2CE2A: fán  # 𬸪  => U+9DED
-        // dbg!(&line);
-        let first = line[0].strip_suffix(":").unwrap();     // U+4CA2
-        let second = line[1]                                    // fán -> fan
-            .to_string()
-            .nfkd()
-            .filter(|c| c.is_ascii())
-            .collect::<String>();
-
-        map.insert(unicode_to_char(&first).unwrap(), second.clone());
-        // 对于一行中存在多个映射结果的情况而言, 构建 yǐ 到不同的解释字形之间的相关关系
+        // U+90FD: dōu dū  # 都
+        // U+957F: zhǎng  # 长 -> cháng
         // U+2CE88: yǐ  # 𬺈  =>  U+9F6E
-        if line.len() >= 6 {
-            if let Some(val) = unicode_to_char(line[5]) {
-                map.insert(val, second);
-            } else {
-                // dbg!(line);
+        let (readings, comment) = line.split_once('#').unwrap_or((&line, ""));
+        let mut readings = readings.split_whitespace();
+        let Some(c) = readings.next().and_then(|code| unicode_to_char(code.strip_suffix(":")?)) else {
+            continue
+        };
+        // 注释的第一项是字本身
+        let syllables: Vec<Syllable> = readings
+            .chain(comment.split_whitespace().skip(1))
+            .filter_map(Syllable::from_pinyin)
+            .collect();
+
+        let variant = comment.split_whitespace().skip_while(|&token| token != "=>").nth(1).and_then(unicode_to_char);
+        for c in std::iter::once(c).chain(variant) {
+            let entry = map.entry(c).or_default();
+            for &syllable in &syllables {
+                if !entry.contains(&syllable) {
+                    entry.push(syllable);
+                }
             }
         }
     }
 
-
     Ok(map)
 }
 
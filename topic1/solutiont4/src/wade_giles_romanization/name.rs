@@ -0,0 +1,104 @@
+//! 人名与地名的书写格式
+//!
+//! # 主要特点:
+//! - 人名: 姓与名分开书写, 各自首字母大写, 名 (以及复姓) 的音节以连字符连接, 如 "Mao Tse-tung"、"Chu-ko Liang"
+//! - 地名: 音节连写, 首字母大写, 如 "Peiching"
+//! - 复姓 (诸葛、欧阳等) 按两个字识别为姓; 作姓时读音特殊的字 (单、曾、解等) 使用姓氏读音
+//!
+//! # 参考文献:
+//! - https://en.wikipedia.org/wiki/Wade%E2%80%93Giles#Usage
+//! - https://en.wikipedia.org/wiki/Chinese_compound_surname
+use super::syllable::Syllable;
+
+/// 转换结果的书写格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// 一般文本: 音节以空格分隔, 句首大写
+    #[default]
+    Text,
+    /// 人名: "Mao Tse-tung"
+    PersonalName,
+    /// 地名: "Peiching"
+    PlaceName,
+}
+
+/// 复姓与作姓时读音特殊的单姓, 含繁体字形
+pub const SURNAMES: &[(&str, &str)] = &[
+    // 复姓
+    ("诸葛", "zhū gě"), ("諸葛", "zhū gě"),
+    ("欧阳", "ōu yáng"), ("歐陽", "ōu yáng"),
+    ("司马", "sī mǎ"), ("司馬", "sī mǎ"),
+    ("上官", "shàng guān"),
+    ("东方", "dōng fāng"), ("東方", "dōng fāng"),
+    ("皇甫", "huáng fǔ"),
+    ("尉迟", "yù chí"), ("尉遲", "yù chí"),
+    ("公孙", "gōng sūn"), ("公孫", "gōng sūn"),
+    ("长孙", "zhǎng sūn"), ("長孫", "zhǎng sūn"),
+    ("慕容", "mù róng"),
+    ("宇文", "yǔ wén"),
+    ("司徒", "sī tú"),
+    ("司空", "sī kōng"),
+    ("夏侯", "xià hóu"),
+    ("轩辕", "xuān yuán"), ("軒轅", "xuān yuán"),
+    ("令狐", "líng hú"),
+    ("钟离", "zhōng lí"), ("鍾離", "zhōng lí"),
+    ("独孤", "dú gū"), ("獨孤", "dú gū"),
+    ("南宫", "nán gōng"), ("南宮", "nán gōng"),
+    ("西门", "xī mén"), ("西門", "xī mén"),
+    ("百里", "bǎi lǐ"),
+    ("呼延", "hū yán"),
+    ("端木", "duān mù"),
+    ("澹台", "tán tái"), ("澹臺", "tán tái"),
+    ("单于", "chán yú"), ("單于", "chán yú"),
+    ("万俟", "mò qí"), ("萬俟", "mò qí"),
+    ("淳于", "chún yú"),
+    ("申屠", "shēn tú"),
+    ("公羊", "gōng yáng"),
+    ("太史", "tài shǐ"),
+    ("闻人", "wén rén"), ("聞人", "wén rén"),
+    ("赫连", "hè lián"), ("赫連", "hè lián"),
+    ("拓跋", "tuò bá"),
+    // 作姓时的读音
+    ("单", "shàn"), ("單", "shàn"),
+    ("曾", "zēng"),
+    ("解", "xiè"),
+    ("仇", "qiú"),
+    ("区", "ōu"), ("區", "ōu"),
+    ("朴", "piáo"),
+    ("查", "zhā"),
+    ("盖", "gě"), ("蓋", "gě"),
+    ("乐", "yuè"), ("樂", "yuè"),
+    ("尉", "wèi"),
+    ("缪", "miào"), ("繆", "miào"),
+    ("翟", "zhái"),
+    ("种", "chóng"), ("種", "chóng"),
+    ("纪", "jǐ"), ("紀", "jǐ"),
+    ("宁", "nìng"), ("寧", "nìng"),
+    ("华", "huà"), ("華", "huà"),
+    ("任", "rén"),
+    ("燕", "yān"),
+    ("过", "guō"), ("過", "guō"),
+    ("覃", "qín"),
+    ("柏", "bǎi"),
+    ("车", "chē"), ("車", "chē"),
+    ("召", "shào"),
+];
+
+/// 将姓名分为姓与名: 前两个字是复姓时姓为两个字, 否则为第一个字
+pub fn split_surname(name: &str) -> (&str, &str) {
+    let mut ends = name.char_indices().map(|(idx, c)| idx + c.len_utf8());
+    let Some(first) = ends.next() else {
+        return ("", "")
+    };
+    match ends.next() {
+        Some(second) if SURNAMES.iter().any(|&(surname, _)| surname == &name[..second]) => name.split_at(second),
+        _ => name.split_at(first),
+    }
+}
+
+/// surname 作姓时的读音, 不在 SURNAMES 中的返回 None
+pub fn surname_reading(surname: &str) -> Option<Vec<Syllable>> {
+    SURNAMES.iter()
+        .find(|&&(name, _)| name == surname)
+        .map(|(_, pinyin)| pinyin.split_whitespace().filter_map(Syllable::from_pinyin).collect())
+}
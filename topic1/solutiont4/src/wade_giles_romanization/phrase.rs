@@ -0,0 +1,117 @@
+//! 基于词语的多音字读音选择
+//!
+//! # 实现原理:
+//! - 词典中每个词语记录了其中每个字在该词中的读音
+//! - 对输入做最少分词: 动态规划求出把输入切分为 "词典中的词语或单个字" 所需的最少段数,
+//!   段数相同时优先让靠前的词语更长, 如 "南京市长江大桥" 切分为 "南京市 / 长江 / 大桥"
+//! - 落在词语中的字使用词语给出的读音, 其余的字保留全部候选读音
+//!
+//! # 参考文献:
+//! - https://github.com/mozillazg/phrase-pinyin-data
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, BufReader, Read};
+use super::syllable::Syllable;
+
+#[derive(Debug, Clone, Default)]
+pub struct PhraseDict {
+    phrases: HashMap<String, Vec<Syllable>>,
+    /// 最长词语的字数
+    max_chars: usize,
+}
+
+impl PhraseDict {
+    /// 每行为 "词语: 读音 读音 ...", 读音数必须与字数相同, `#` 开头的行为注释
+    pub fn parse(reader: impl Read) -> io::Result<Self> {
+        let mut dict = PhraseDict::default();
+        for (idx, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {message}", idx + 1));
+
+            let (phrase, readings) = line.split_once(':').ok_or_else(|| invalid(format!("missing ':' in '{line}'")))?;
+            let phrase = phrase.trim();
+            let syllables = readings.split_whitespace()
+                .map(|pinyin| Syllable::from_pinyin(pinyin).ok_or_else(|| invalid(format!("'{pinyin}' is not a pinyin syllable"))))
+                .collect::<io::Result<Vec<Syllable>>>()?;
+            let chars = phrase.chars().count();
+            if chars != syllables.len() {
+                return Err(invalid(format!("'{phrase}' has {chars} characters but {} readings", syllables.len())));
+            }
+            dict.max_chars = dict.max_chars.max(chars);
+            dict.phrases.insert(phrase.to_string(), syllables);
+        }
+        Ok(dict)
+    }
+
+    pub fn get(&self, phrase: &str) -> Option<&[Syllable]> {
+        self.phrases.get(phrase).map(Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize {
+        self.phrases.len()
+    }
+
+    /// 全部词语与读音, 顺序不确定
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Syllable])> + '_ {
+        self.phrases.iter().map(|(phrase, syllables)| (phrase.as_str(), syllables.as_slice()))
+    }
+
+    /// 全部词语中每个字出现过的读音
+    pub fn char_readings(&self) -> impl Iterator<Item = (char, Syllable)> + '_ {
+        self.phrases.iter().flat_map(|(phrase, syllables)| phrase.chars().zip(syllables.iter().copied()))
+    }
+
+    /// 最少分词, 返回每一段的字数
+    pub fn segment(&self, chars: &[char]) -> Vec<usize> {
+        let n = chars.len();
+        // best[i]: chars[i..] 的最少段数, next[i]: 从 i 开始的一段的字数
+        let mut best = vec![0_usize; n + 1];
+        let mut next = vec![1_usize; n + 1];
+        let mut key = String::new();
+        for i in (0..n).rev() {
+            best[i] = usize::MAX;
+            for len in (2..=self.max_chars.min(n - i)).rev() {
+                key.clear();
+                key.extend(&chars[i..i + len]);
+                if best[i + len] + 1 < best[i] && self.phrases.contains_key(&key) {
+                    best[i] = best[i + len] + 1;
+                    next[i] = len;
+                }
+            }
+            // 段数相同时词语优先于单字
+            if best[i + 1] + 1 < best[i] {
+                best[i] = best[i + 1] + 1;
+                next[i] = 1;
+            }
+        }
+
+        let mut res = Vec::with_capacity(best[0]);
+        let mut i = 0;
+        while i < n {
+            res.push(next[i]);
+            i += next[i];
+        }
+        res
+    }
+
+    /// 每个字的候选读音, 落在词语中的字只有词语给出的一个读音, 其余的字取 chars 中的全部读音
+    pub fn readings(&self, text: &str, chars: &HashMap<char, Vec<Syllable>>) -> Vec<(char, Vec<Syllable>)> {
+        let text: Vec<char> = text.chars().collect();
+        let mut res = Vec::with_capacity(text.len());
+        let mut start = 0;
+        for len in self.segment(&text) {
+            let segment = &text[start..start + len];
+            match self.get(&segment.iter().collect::<String>()) {
+                Some(syllables) if len > 1 => {
+                    res.extend(segment.iter().zip(syllables).map(|(&c, &syllable)| (c, vec![syllable])));
+                }
+                _ => res.push((segment[0], chars.get(&segment[0]).cloned().unwrap_or_default())),
+            }
+            start += len;
+        }
+        res
+    }
+}
@@ -0,0 +1,318 @@
+//! 由威妥玛拼音或汉语拼音反查汉字
+//!
+//! # 主要特点:
+//! - 输入可以带或不带声调 (拼音声调符号、数字或威妥玛的上标数字), 可以省略送气符号
+//! - 音节之间可以用空格、连字符分隔, 也可以连写 (如 "Peiching", "xi'an")
+//! - 每个音节同时按威妥玛拼音与汉语拼音解析, 并容忍常见的历史拼写变体, 如 Shi/Hsi、Sze/Ssu、Kwang/Kuang
+//! - 给出全部可能的音节切分 (音节数少的在前) 以及排好序的汉字候选
+//!
+//! # 实现原理:
+//! - 切分: 对每个位置求出所有能解析为音节的片段, 先求出每个位置能否到达结尾, 再深度优先枚举切分
+//! - 候选: 与某种切分逐个音节吻合的词语排在最前; 其余按每个位置的单字排名之和从小到大组合,
+//!   单字的排名依次比较: 字频排名、是否收录于《通用规范汉字表》、该读音是否为这个字的首选读音
+//!
+//! # 参考文献:
+//! - https://en.wikipedia.org/wiki/Chinese_postal_romanization
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::io;
+use std::io::{BufRead, BufReader, Read};
+use super::Converter;
+use super::scheme::{Romanization, WadeGiles};
+use super::syllable::{strip_tone_marks, Syllable};
+
+/// 一个音节片段可能的最大字符数, 如 "ch'uang⁴"
+const MAX_PIECE_CHARS: usize = 8;
+
+/// 最多给出的切分数
+pub const MAX_SEGMENTATIONS: usize = 16;
+
+/// 替换音节开头的历史拼写变体, 以及省略了送气符号的写法
+const INITIAL_VARIANTS: &[(&str, &str)] = &[
+    ("sh", "hs"),   // Shi → Hsi
+    ("sz", "ss"),   // Szu → Ssu
+    ("kw", "ku"),   // Kwang → Kuang
+    ("hw", "hu"),   // Hwang → Huang
+    ("ki", "chi"),  // 邮政式拼音: Peking → Pei-ching
+    ("ki", "ch'i"), // Chungking → Ch'ung-ch'ing
+    ("tsi", "chi"), // Tsinan → Chi-nan
+    ("tsi", "ch'i"),// Tsingtao → Ch'ing-tao
+    ("si", "hsi"),  // Sian → Hsi-an
+    ("ch", "ch'"),
+    ("k", "k'"),
+    ("p", "p'"),
+    ("t", "t'"),
+    ("ts", "ts'"),
+    ("tz", "tz'"),
+];
+
+/// 替换整个音节的历史拼写变体
+const SYLLABLE_VARIANTS: &[(&str, &str)] = &[
+    ("pe", "pei"),
+    ("sze", "ssu"),
+    ("tsze", "tzu"),
+    ("tze", "tzu"),
+    ("tsz", "tzu"),
+];
+
+/// 替换音节中任意位置的变体, 如省略 ü 的附加符号
+const FINAL_VARIANTS: &[(&str, &str)] = &[
+    ("u", "ü"),
+    ("ow", "ou"),
+    ("ie", "ieh"),
+    ("ue", "üeh"),
+];
+
+/// 单字的排名: (字频排名, 是否未收录于《通用规范汉字表》, 读音在该字读音中的序号, 字)
+type Rank = (usize, bool, usize, char);
+
+/// 一个音节片段: 原文与可能的音节 (精确的解析在前, 变体在后)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piece {
+    pub text: String,
+    pub syllables: Vec<Syllable>,
+}
+
+/// 一个汉字候选与各字所取的读音
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub hanzi: String,
+    pub syllables: Vec<Syllable>,
+}
+
+/// 解析字频表: 按出现的先后给每个字编排名, `#` 开头的行为注释
+pub fn parse_frequency(reader: impl Read) -> io::Result<HashMap<char, usize>> {
+    let mut res = HashMap::new();
+    for line in BufReader::new(reader).lines() {
+        let line = line?;
+        if line.starts_with('#') {
+            continue;
+        }
+        for c in line.chars().filter(|c| !c.is_whitespace()) {
+            let rank = res.len();
+            res.entry(c).or_insert(rank);
+        }
+    }
+    Ok(res)
+}
+
+/// 分出声调, 返回 (小写且统一了送气符号的文本, 声调)
+fn split_tone(text: &str) -> (String, Option<u8>) {
+    let text = WadeGiles::normalize(text);
+    match text.chars().last() {
+        Some(c @ '1'..='5') => (text[..text.len() - 1].to_string(), c.to_digit(10).map(|d| d as u8)),
+        Some(c) => match WadeGiles::TONES.iter().position(|&mark| mark == c) {
+            Some(idx) => (text[..text.len() - c.len_utf8()].to_string(), Some(idx as u8 + 1)),
+            None => strip_tone_marks(&text),
+        },
+        None => (text, None),
+    }
+}
+
+/// 不计声调的拼写可能对应的音节: 先威妥玛后拼音
+fn decode_toneless(text: &str) -> impl Iterator<Item = Syllable> {
+    WadeGiles.decode_all(text).into_iter().chain(Syllable::from_pinyin(text))
+}
+
+/// 一个音节的拼写可能对应的全部音节, 精确的解析在前, 拼写变体在后, 不是音节时为空
+pub fn decode_syllable(text: &str) -> Vec<Syllable> {
+    let (body, tone) = split_tone(text);
+    if body.is_empty() || body.chars().any(|c| c.is_ascii_digit()) {
+        return Vec::new();
+    }
+
+    let mut spellings = vec![body.clone()];
+    spellings.extend(SYLLABLE_VARIANTS.iter().filter(|&&(from, _)| from == body).map(|(_, to)| to.to_string()));
+    spellings.extend(INITIAL_VARIANTS.iter()
+        .filter_map(|(from, to)| body.strip_prefix(from).map(|rest| format!("{to}{rest}"))));
+    let variants: Vec<String> = spellings.iter()
+        .flat_map(|spelling| FINAL_VARIANTS.iter()
+            .filter(|(from, _)| spelling.contains(from))
+            .map(|(from, to)| spelling.replacen(from, to, 1)))
+        .collect();
+    spellings.extend(variants);
+
+    let mut res: Vec<Syllable> = Vec::new();
+    for syllable in spellings.iter().flat_map(|spelling| decode_toneless(spelling)) {
+        let syllable = syllable.with_tone(tone);
+        if !res.contains(&syllable) {
+            res.push(syllable);
+        }
+    }
+    res
+}
+
+/// input 的音节切分, 音节数少的在前, 最多 MAX_SEGMENTATIONS 种, 无法切分时为空
+pub fn segmentations(input: &str) -> Vec<Vec<Piece>> {
+    let chars: Vec<char> = input.trim().chars().collect();
+    let n = chars.len();
+    let is_separator = |c: char| c.is_whitespace() || matches!(c, '-' | '_' | ',' | '.' | '·');
+
+    // pieces[i]: 从 i 开始的片段 (音节的起止位置, 可能的音节), 长的在前; 拼音的隔音符号 ' 可以出现在片段开头
+    let pieces: Vec<Vec<(usize, usize, Vec<Syllable>)>> = (0..n)
+        .map(|i| {
+            let start = if chars[i] == '\'' && i > 0 && !is_separator(chars[i - 1]) { i + 1 } else { i };
+            (start + 1..=(start + MAX_PIECE_CHARS).min(n))
+                .filter(|&end| !chars[start..end].iter().any(|&c| is_separator(c)))
+                .rev()
+                .filter_map(|end| {
+                    let syllables = decode_syllable(&chars[start..end].iter().collect::<String>());
+                    (!syllables.is_empty()).then_some((start, end, syllables))
+                })
+                .collect()
+        })
+        .collect();
+
+    // 跳过分隔符后的位置, 以及每个位置能否切分到结尾
+    let skip = |mut i: usize| {
+        while i < n && is_separator(chars[i]) {
+            i += 1;
+        }
+        i
+    };
+    let mut reachable = vec![false; n + 1];
+    reachable[n] = true;
+    for i in (0..n).rev() {
+        reachable[i] = if is_separator(chars[i]) {
+            reachable[skip(i)]
+        } else {
+            pieces[i].iter().any(|&(_, end, _)| reachable[skip(end)])
+        };
+    }
+
+    let mut res = Vec::new();
+    let start = skip(0);
+    if start < n && reachable[start] {
+        let next = |i: usize| -> Vec<(usize, Piece)> {
+            pieces[i].iter()
+                .filter(|&&(_, end, _)| reachable[skip(end)])
+                .map(|(start, end, syllables)| {
+                    let text = chars[*start..*end].iter().collect();
+                    (skip(*end), Piece { text, syllables: syllables.clone() })
+                })
+                .collect()
+        };
+        enumerate(start, n, &next, &mut Vec::new(), &mut res);
+    }
+    res.sort_by_key(Vec::len);
+    res
+}
+
+/// 深度优先枚举从 i 开始的切分, 结果达到 MAX_SEGMENTATIONS 种时停止
+fn enumerate(i: usize, n: usize, next: &dyn Fn(usize) -> Vec<(usize, Piece)>, path: &mut Vec<Piece>, res: &mut Vec<Vec<Piece>>) {
+    if res.len() >= MAX_SEGMENTATIONS {
+        return;
+    }
+    if i >= n {
+        res.push(path.clone());
+        return;
+    }
+    for (end, piece) in next(i) {
+        path.push(piece);
+        enumerate(end, n, next, path, res);
+        path.pop();
+    }
+}
+
+impl Converter {
+    /// 不计声调的音节到汉字的索引, 每个音节下的字按排名排列
+    fn hanzi_index(&self) -> &HashMap<Syllable, Vec<(char, Syllable)>> {
+        self.index.get_or_init(|| {
+            let mut ranked: HashMap<Syllable, Vec<(Rank, Syllable)>> = HashMap::new();
+            for (&c, syllables) in &self.readings {
+                for (idx, &syllable) in syllables.iter().enumerate() {
+                    ranked.entry(syllable.with_tone(None))
+                        .or_default()
+                        .push(((self.frequency.get(&c).copied().unwrap_or(usize::MAX), !self.common.contains(&c), idx, c), syllable));
+                }
+            }
+            ranked.into_iter()
+                .map(|(key, mut chars)| {
+                    chars.sort_unstable_by_key(|&(rank, _)| rank);
+                    (key, chars.into_iter().map(|((.., c), syllable)| (c, syllable)).collect())
+                })
+                .collect()
+        })
+    }
+
+    /// 读作 syllable 的汉字, 按排名排列; 未标声调时不限声调
+    pub fn hanzi_of(&self, syllable: Syllable) -> Vec<(char, Syllable)> {
+        self.hanzi_index()
+            .get(&syllable.with_tone(None))
+            .map(|chars| chars.iter()
+                .filter(|(_, reading)| syllable.tone().is_none_or(|tone| reading.tone() == Some(tone)))
+                .copied()
+                .collect())
+            .unwrap_or_default()
+    }
+
+    /// 与 piece 吻合的汉字, 先按音节的先后, 再按排名排列, 最多 limit 个
+    fn piece_hanzi(&self, piece: &Piece, limit: usize) -> Vec<(char, Syllable)> {
+        let mut res: Vec<(char, Syllable)> = Vec::new();
+        for &syllable in &piece.syllables {
+            for (c, reading) in self.hanzi_of(syllable) {
+                if res.len() >= limit {
+                    return res;
+                }
+                if !res.iter().any(|&(other, _)| other == c) {
+                    res.push((c, reading));
+                }
+            }
+        }
+        res
+    }
+
+    /// 由威妥玛拼音或汉语拼音反查汉字, 给出最多 limit 个排好序的候选
+    pub fn hanzi_candidates(&self, input: &str, limit: usize) -> Vec<Candidate> {
+        let mut res: Vec<Candidate> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut push = |res: &mut Vec<Candidate>, candidate: Candidate| {
+            if res.len() < limit && seen.insert(candidate.hanzi.clone()) {
+                res.push(candidate);
+            }
+        };
+
+        let segmentations = segmentations(input);
+        // 与某种切分吻合的词语
+        for pieces in &segmentations {
+            let matches = |phrase: &[Syllable]| {
+                phrase.len() == pieces.len() && phrase.iter().zip(pieces).all(|(reading, piece)| {
+                    piece.syllables.iter().any(|syllable| match syllable.tone() {
+                        Some(_) => syllable == reading,
+                        None => syllable.with_tone(None) == reading.with_tone(None),
+                    })
+                })
+            };
+            let mut phrases: Vec<(&str, &[Syllable])> = self.phrases.iter().filter(|(_, syllables)| matches(syllables)).collect();
+            phrases.sort_unstable_by_key(|&(phrase, _)| phrase);
+            for (phrase, syllables) in phrases {
+                push(&mut res, Candidate { hanzi: phrase.to_string(), syllables: syllables.to_vec() });
+            }
+        }
+
+        // 按单字排名之和组合
+        for pieces in &segmentations {
+            let choices: Vec<Vec<(char, Syllable)>> = pieces.iter().map(|piece| self.piece_hanzi(piece, limit)).collect();
+            if choices.iter().any(Vec::is_empty) {
+                continue;
+            }
+            let mut heap = BinaryHeap::from([Reverse((0_usize, vec![0_usize; choices.len()]))]);
+            let mut visited = HashSet::new();
+            while let Some(Reverse((cost, ranks))) = heap.pop() {
+                if res.len() >= limit {
+                    return res;
+                }
+                let (hanzi, syllables) = ranks.iter().zip(&choices).map(|(&rank, choice)| choice[rank]).unzip();
+                push(&mut res, Candidate { hanzi, syllables });
+                for pos in 0..ranks.len() {
+                    let mut next = ranks.clone();
+                    next[pos] += 1;
+                    if next[pos] < choices[pos].len() && visited.insert(next.clone()) {
+                        heap.push(Reverse((cost + 1, next)));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
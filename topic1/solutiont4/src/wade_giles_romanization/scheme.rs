@@ -0,0 +1,550 @@
+//! 罗马化与注音方案
+//!
+//! # 主要特点:
+//! - 每种方案实现 Romanization: 将 Syllable 拼写为文本, 以及将文本解析回 Syllable
+//! - 方案之间的转换经由 Syllable 进行, 不需要回到汉字
+//! - 解析时忽略大小写; 一种拼写可能对应多个音节 (如威妥玛的 lo 可以是 luo 或 lo), 按常用程度排列
+//!
+//! # 支持的方案:
+//! - 汉语拼音 (声调符号 / 声调数字)
+//! - 威妥玛拼音 (Wade–Giles), 送气符号为 ', 声调为上标数字
+//! - 国语罗马字 (Gwoyeu Romatzyh), 以拼写变化表示声调
+//! - 耶鲁拼音 (Yale), 声调符号与汉语拼音相同
+//! - 注音符号 (Zhuyin / Bopomofo), 一声不标, 轻声的 ˙ 写在前面
+//!
+//! # 参考文献:
+//! - https://en.wikipedia.org/wiki/Wade%E2%80%93Giles
+//! - https://en.wikipedia.org/wiki/Gwoyeu_Romatzyh
+//! - https://en.wikipedia.org/wiki/Yale_romanization_of_Mandarin
+//! - https://en.wikipedia.org/wiki/Bopomofo
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::OnceLock;
+use super::syllable::{place_tone_mark, strip_tone_marks, Syllable};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RomanizationError {
+    UnknownSyllable { scheme: &'static str, text: String }, // 不是该方案中的音节
+}
+
+impl Display for RomanizationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RomanizationError::UnknownSyllable { scheme, text } => write!(f, "'{text}' is not a {scheme} syllable"),
+        }
+    }
+}
+
+impl std::error::Error for RomanizationError { }
+
+pub trait Romanization {
+    fn name(&self) -> &'static str;
+
+    /// 拼写一个音节, 声调为 None 时不标声调
+    fn encode(&self, syllable: &Syllable) -> String;
+
+    /// text 可能对应的全部音节, 常用的在前, 不是该方案中的音节时为空
+    fn decode_all(&self, text: &str) -> Vec<Syllable>;
+
+    fn decode(&self, text: &str) -> Option<Syllable> {
+        self.decode_all(text).into_iter().next()
+    }
+
+    /// 音节之间的分隔符
+    fn is_separator(&self, c: char) -> bool {
+        c.is_whitespace() || c == '-'
+    }
+}
+
+pub struct PinyinToneMarks;
+pub struct PinyinToneNumbers;
+pub struct WadeGiles;
+pub struct GwoyeuRomatzyh;
+pub struct Yale;
+pub struct Zhuyin;
+
+/// 全部内置的方案
+pub const SCHEMES: [&dyn Romanization; 6] = [&PinyinToneMarks, &PinyinToneNumbers, &WadeGiles, &GwoyeuRomatzyh, &Yale, &Zhuyin];
+
+/// 按名称 (忽略大小写) 查找内置的方案
+pub fn find_scheme(name: &str) -> Option<&'static dyn Romanization> {
+    SCHEMES.into_iter().find(|scheme| scheme.name().eq_ignore_ascii_case(name.trim()))
+}
+
+/// 将 from 方案书写的 text 逐个音节转换为 to 方案, 分隔符原样保留, 首字母大写的音节转换后仍首字母大写
+pub fn convert(text: &str, from: &dyn Romanization, to: &dyn Romanization) -> Result<String, RomanizationError> {
+    let mut res = String::with_capacity(text.len());
+    let mut token = String::new();
+    for c in text.chars().chain(std::iter::once(' ')) {
+        if !from.is_separator(c) {
+            token.push(c);
+            continue;
+        }
+        if !token.is_empty() {
+            let syllable = from.decode(&token).ok_or_else(|| RomanizationError::UnknownSyllable {
+                scheme: from.name(),
+                text: token.clone(),
+            })?;
+            let encoded = to.encode(&syllable);
+            if token.starts_with(char::is_uppercase) {
+                res.push_str(&capitalize(&encoded));
+            } else {
+                res.push_str(&encoded);
+            }
+            token.clear();
+        }
+        res.push(c);
+    }
+    res.pop();
+    Ok(res)
+}
+
+/// 将首字母转换为大写
+pub fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+type ReverseTable = HashMap<String, Vec<Syllable>>;
+
+/// 以 scheme 拼写的结果 (经 key 规范化) 为键的反查表, 每个键下的音节按 Syllable::all 的顺序排列
+fn reverse_table(scheme: &dyn Romanization, tones: &[Option<u8>], key: impl Fn(&str) -> String) -> ReverseTable {
+    let mut table = ReverseTable::new();
+    for &tone in tones {
+        for syllable in Syllable::all() {
+            let syllable = syllable.with_tone(tone);
+            table.entry(key(&scheme.encode(&syllable))).or_default().push(syllable);
+        }
+    }
+    table
+}
+
+fn lookup(table: &ReverseTable, key: &str, tone: Option<u8>) -> Vec<Syllable> {
+    table.get(key)
+        .map(|syllables| syllables.iter().map(|syllable| syllable.with_tone(tone)).collect())
+        .unwrap_or_default()
+}
+
+impl Romanization for PinyinToneMarks {
+    fn name(&self) -> &'static str {
+        "pinyin"
+    }
+
+    fn encode(&self, syllable: &Syllable) -> String {
+        place_tone_mark(&syllable.pinyin(), syllable.tone().filter(|&tone| tone != 5))
+    }
+
+    fn decode_all(&self, text: &str) -> Vec<Syllable> {
+        Syllable::from_pinyin(text).into_iter().collect()
+    }
+
+    fn is_separator(&self, c: char) -> bool {
+        c.is_whitespace() || c == '-' || c == '\''
+    }
+}
+
+impl Romanization for PinyinToneNumbers {
+    fn name(&self) -> &'static str {
+        "pinyin-numbers"
+    }
+
+    /// ü 写作 v, 只使用 ASCII 字符
+    fn encode(&self, syllable: &Syllable) -> String {
+        let pinyin = syllable.pinyin().replace('ü', "v");
+        match syllable.tone() {
+            Some(tone) => format!("{pinyin}{tone}"),
+            None => pinyin,
+        }
+    }
+
+    fn decode_all(&self, text: &str) -> Vec<Syllable> {
+        Syllable::from_pinyin(text).into_iter().collect()
+    }
+
+    fn is_separator(&self, c: char) -> bool {
+        c.is_whitespace() || c == '-' || c == '\''
+    }
+}
+
+impl WadeGiles {
+    pub(super) const TONES: [char; 4] = ['¹', '²', '³', '⁴'];
+
+    /// 忽略大小写、送气符号的不同写法与 ê 的附加符号
+    pub(super) fn normalize(text: &str) -> String {
+        text.to_lowercase()
+            .chars()
+            .map(|c| match c {
+                '’' | '‘' | 'ʻ' | 'ʼ' | '`' => '\'',
+                'ê' => 'e',
+                _ => c,
+            })
+            .collect()
+    }
+}
+
+impl Romanization for WadeGiles {
+    fn name(&self) -> &'static str {
+        "wade-giles"
+    }
+
+    fn encode(&self, syllable: &Syllable) -> String {
+        let (initial, rime) = (syllable.initial(), syllable.rime());
+        let mut res = String::from(match (initial, rime) {
+            ("z", "-i") => "tz",
+            ("c", "-i") => "tz'",
+            ("s", "-i") => "ss",
+            ("b", _) => "p",
+            ("p", _) => "p'",
+            ("d", _) => "t",
+            ("t", _) => "t'",
+            ("g", _) => "k",
+            ("k", _) => "k'",
+            ("j" | "zh", _) => "ch",
+            ("q" | "ch", _) => "ch'",
+            ("x", _) => "hs",
+            ("r", _) => "j",
+            ("z", _) => "ts",
+            ("c", _) => "ts'",
+            _ => initial,
+        });
+
+        let rime = if initial.is_empty() {
+            match rime {
+                "e" => "o",
+                "ê" => "eh",
+                "en" => "ên",
+                "eng" => "êng",
+                "er" => "êrh",
+                "ia" => "ya",
+                "io" => "yo",
+                "ie" => "yeh",
+                "iai" => "yai",
+                "iao" => "yao",
+                "iou" => "yu",
+                "ian" => "yen",
+                "in" => "yin",
+                "iang" => "yang",
+                "ing" => "ying",
+                "iong" => "yung",
+                "u" => "wu",
+                "ua" => "wa",
+                "uo" => "wo",
+                "uai" => "wai",
+                "uei" => "wei",
+                "uan" => "wan",
+                "uen" => "wên",
+                "uang" => "wang",
+                "ueng" => "wêng",
+                "ü" => "yü",
+                "üe" => "yüeh",
+                "üan" => "yüan",
+                "ün" => "yün",
+                _ => rime,
+            }
+        } else {
+            match (initial, rime) {
+                ("z" | "c" | "s", "-i") => "u",
+                (_, "-i") => "ih",
+                ("g" | "k" | "h", "e") => "o",
+                (_, "e") => "ê",
+                (_, "ê") => "eh",
+                (_, "en") => "ên",
+                (_, "eng") => "êng",
+                (_, "er") => "êrh",
+                (_, "ong") => "ung",
+                (_, "ie") => "ieh",
+                (_, "iou") => "iu",
+                (_, "ian") => "ien",
+                (_, "iong") => "iung",
+                ("g" | "k" | "h" | "sh", "uo") => "uo",
+                (_, "uo") => "o",
+                ("g" | "k", "uei") => "uei",
+                (_, "uei") => "ui",
+                (_, "uen") => "un",
+                (_, "üe") => "üeh",
+                _ => rime,
+            }
+        };
+        res.push_str(rime);
+
+        if let Some(&mark) = syllable.tone().and_then(|tone| Self::TONES.get(tone as usize - 1)) {
+            res.push(mark);
+        }
+        res
+    }
+
+    /// 声调可以是上标数字或普通数字
+    fn decode_all(&self, text: &str) -> Vec<Syllable> {
+        static TABLE: OnceLock<ReverseTable> = OnceLock::new();
+        let table = TABLE.get_or_init(|| reverse_table(self, &[None], Self::normalize));
+
+        let text = Self::normalize(text.trim());
+        let (body, tone) = match text.chars().last() {
+            Some(c @ '1'..='5') => (&text[..text.len() - 1], c.to_digit(10).map(|d| d as u8)),
+            Some(c) => match Self::TONES.iter().position(|&mark| mark == c) {
+                Some(idx) => (&text[..text.len() - c.len_utf8()], Some(idx as u8 + 1)),
+                None => (text.as_str(), None),
+            },
+            None => return Vec::new(),
+        };
+        lookup(table, body, tone)
+    }
+}
+
+impl GwoyeuRomatzyh {
+    /// 基本形式 (阴平) 的韵母
+    fn base_rime(syllable: &Syllable) -> &'static str {
+        match syllable.rime() {
+            "ê" => "è",
+            "ao" => "au",
+            "er" => "el",
+            "-i" => "y",
+            "iao" => "iau",
+            "ü" => "iu",
+            "üe" => "iue",
+            "üan" => "iuan",
+            "ün" => "iun",
+            rime => rime,
+        }
+    }
+
+    /// 阳平: 以 i u 开头的韵母将 i u 改为 y w, 其余在主要元音之后加 r
+    fn second_tone(rime: &str) -> String {
+        match rime {
+            "i" => "yi".to_string(),
+            "u" => "wu".to_string(),
+            "in" => "yn".to_string(),
+            "ing" => "yng".to_string(),
+            "y" => "yr".to_string(),
+            _ if rime.starts_with("iu") => format!("yu{}", &rime[2..]),
+            _ if rime.starts_with('i') => format!("y{}", &rime[1..]),
+            _ if rime.starts_with('u') => format!("w{}", &rime[1..]),
+            _ => {
+                let end = rime.find(|c: char| !"aeiou".contains(c)).unwrap_or(rime.len());
+                format!("{}r{}", &rime[..end], &rime[end..])
+            }
+        }
+    }
+
+    /// 上声: 介音 i u 改为 e o, 没有介音时双写主要元音, ai au 改为 ae ao
+    fn third_tone(rime: &str) -> String {
+        match rime {
+            "i" | "u" | "y" => rime.repeat(2),
+            "iu" => "eu".to_string(),
+            "in" => "iin".to_string(),
+            "ing" => "iing".to_string(),
+            "ie" => "iee".to_string(),
+            "io" => "ioo".to_string(),
+            "uo" => "uoo".to_string(),
+            "ai" => "ae".to_string(),
+            "au" => "ao".to_string(),
+            _ if rime.starts_with("iu") => format!("eu{}", &rime[2..]),
+            _ if rime.starts_with('i') => format!("e{}", &rime[1..]),
+            _ if rime.starts_with('u') => format!("o{}", &rime[1..]),
+            _ => {
+                let first = rime.chars().next().unwrap_or_default();
+                format!("{first}{rime}")
+            }
+        }
+    }
+
+    /// 去声: 韵尾 i u n ng l 改为 y w nn nq ll, 其余加 h
+    fn fourth_tone(rime: &str, base: &str) -> String {
+        let glide_end = base.len() > 1 && base != "iu" && (base.ends_with('i') || base.ends_with('u'));
+        if glide_end {
+            let (head, last) = rime.split_at(rime.len() - 1);
+            return format!("{head}{}", if last == "i" { "y" } else { "w" });
+        }
+        if let Some(head) = rime.strip_suffix("ng") {
+            format!("{head}nq")
+        } else if rime.ends_with('n') {
+            format!("{rime}n")
+        } else if rime.ends_with('l') {
+            format!("{rime}l")
+        } else {
+            format!("{rime}h")
+        }
+    }
+}
+
+impl Romanization for GwoyeuRomatzyh {
+    fn name(&self) -> &'static str {
+        "gwoyeu-romatzyh"
+    }
+
+    /// 轻声与未标注的声调使用基本形式
+    fn encode(&self, syllable: &Syllable) -> String {
+        let initial = match syllable.initial() {
+            "zh" => "j",
+            "q" => "ch",
+            "x" => "sh",
+            "z" => "tz",
+            "c" => "ts",
+            initial => initial,
+        };
+        let base = Self::base_rime(syllable);
+        let sonorant = matches!(initial, "m" | "n" | "l" | "r");
+        let zero = initial.is_empty();
+
+        let rime = match syllable.tone() {
+            Some(1) if sonorant => return format!("{initial}h{base}"),
+            Some(2) if !sonorant => Self::second_tone(base),
+            Some(3) => {
+                let rime = Self::third_tone(base);
+                match rime.as_str() {
+                    _ if !zero => rime,
+                    "iee" | "ioo" => format!("y{}", &rime[1..]),
+                    "uoo" => format!("w{}", &rime[1..]),
+                    _ if base.starts_with('i') => format!("y{rime}"),
+                    _ if base.starts_with('u') => format!("w{rime}"),
+                    _ => rime,
+                }
+            }
+            Some(4) if zero && (base.starts_with('i') || base.starts_with('u')) => {
+                // 零声母先按拼音的规则写出 y w, 再变换韵尾
+                let spelled = match base {
+                    "i" | "in" | "ing" => format!("y{base}"),
+                    "u" => "wu".to_string(),
+                    _ if base.starts_with("iu") => format!("yu{}", &base[2..]),
+                    _ if base.starts_with('i') => format!("y{}", &base[1..]),
+                    _ => format!("w{}", &base[1..]),
+                };
+                Self::fourth_tone(&spelled, base)
+            }
+            Some(4) => Self::fourth_tone(base, base),
+            _ => base.to_string(),
+        };
+        format!("{initial}{rime}")
+    }
+
+    fn decode_all(&self, text: &str) -> Vec<Syllable> {
+        static TABLE: OnceLock<ReverseTable> = OnceLock::new();
+        let table = TABLE.get_or_init(|| reverse_table(self, &[Some(1), Some(2), Some(3), Some(4)], str::to_string));
+        table.get(&text.trim().to_lowercase()).cloned().unwrap_or_default()
+    }
+}
+
+impl Romanization for Yale {
+    fn name(&self) -> &'static str {
+        "yale"
+    }
+
+    fn encode(&self, syllable: &Syllable) -> String {
+        let (initial, rime) = (syllable.initial(), syllable.rime());
+        let yale_rime = match (initial, rime) {
+            ("zh" | "ch" | "sh", "-i") => "r",
+            ("r" | "z", "-i") => "",
+            ("c" | "s", "-i") => "z",
+            ("b" | "p" | "m" | "f", "o") => "wo",
+            ("", "i") => "yi",
+            ("", "in") => "yin",
+            ("", "ing") => "ying",
+            ("", "u") => "wu",
+            ("", "uen") => "wen",
+            (_, "uen") => "wun",
+            (_, "ao") => "au",
+            (_, "ong") => "ung",
+            (_, "io") => "yo",
+            (_, "ia") => "ya",
+            (_, "ie") => "ye",
+            (_, "iai") => "yai",
+            (_, "iao") => "yau",
+            (_, "iou") => "you",
+            (_, "ian") => "yan",
+            (_, "iang") => "yang",
+            (_, "iong") => "yung",
+            (_, "ua") => "wa",
+            (_, "uo") => "wo",
+            (_, "uai") => "wai",
+            (_, "uei") => "wei",
+            (_, "uan") => "wan",
+            (_, "uang") => "wang",
+            (_, "ueng") => "weng",
+            (_, "ü") => "yu",
+            (_, "üe") => "ywe",
+            (_, "üan") => "ywan",
+            (_, "ün") => "yun",
+            _ => rime,
+        };
+        let spelled = match initial {
+            // x 写作 sy, 与韵母开头的 y 合并
+            "x" => format!("sy{}", yale_rime.strip_prefix('y').unwrap_or(yale_rime)),
+            _ => {
+                let initial = match initial {
+                    "q" => "ch",
+                    "zh" => "j",
+                    "z" => "dz",
+                    "c" => "ts",
+                    initial => initial,
+                };
+                format!("{initial}{yale_rime}")
+            }
+        };
+        place_tone_mark(&spelled, syllable.tone().filter(|&tone| tone != 5))
+    }
+
+    fn decode_all(&self, text: &str) -> Vec<Syllable> {
+        static TABLE: OnceLock<ReverseTable> = OnceLock::new();
+        let table = TABLE.get_or_init(|| reverse_table(self, &[None], str::to_string));
+        let (body, tone) = strip_tone_marks(&text.trim().to_lowercase());
+        lookup(table, &body, tone)
+    }
+}
+
+impl Zhuyin {
+    const TONES: [char; 4] = ['ˉ', 'ˊ', 'ˇ', 'ˋ'];
+    const NEUTRAL: char = '˙';
+}
+
+impl Romanization for Zhuyin {
+    fn name(&self) -> &'static str {
+        "zhuyin"
+    }
+
+    fn encode(&self, syllable: &Syllable) -> String {
+        let initial = match syllable.initial() {
+            "b" => "ㄅ", "p" => "ㄆ", "m" => "ㄇ", "f" => "ㄈ",
+            "d" => "ㄉ", "t" => "ㄊ", "n" => "ㄋ", "l" => "ㄌ",
+            "g" => "ㄍ", "k" => "ㄎ", "h" => "ㄏ",
+            "j" => "ㄐ", "q" => "ㄑ", "x" => "ㄒ",
+            "zh" => "ㄓ", "ch" => "ㄔ", "sh" => "ㄕ", "r" => "ㄖ",
+            "z" => "ㄗ", "c" => "ㄘ", "s" => "ㄙ",
+            _ => "",
+        };
+        let rime = match syllable.rime() {
+            "a" => "ㄚ", "o" => "ㄛ", "e" => "ㄜ", "ê" => "ㄝ",
+            "ai" => "ㄞ", "ei" => "ㄟ", "ao" => "ㄠ", "ou" => "ㄡ",
+            "an" => "ㄢ", "en" => "ㄣ", "ang" => "ㄤ", "eng" => "ㄥ", "ong" => "ㄨㄥ", "er" => "ㄦ",
+            "-i" => "",
+            "i" => "ㄧ", "ia" => "ㄧㄚ", "io" => "ㄧㄛ", "ie" => "ㄧㄝ", "iai" => "ㄧㄞ", "iao" => "ㄧㄠ",
+            "iou" => "ㄧㄡ", "ian" => "ㄧㄢ", "in" => "ㄧㄣ", "iang" => "ㄧㄤ", "ing" => "ㄧㄥ", "iong" => "ㄩㄥ",
+            "u" => "ㄨ", "ua" => "ㄨㄚ", "uo" => "ㄨㄛ", "uai" => "ㄨㄞ", "uei" => "ㄨㄟ",
+            "uan" => "ㄨㄢ", "uen" => "ㄨㄣ", "uang" => "ㄨㄤ", "ueng" => "ㄨㄥ",
+            "ü" => "ㄩ", "üe" => "ㄩㄝ", "üan" => "ㄩㄢ", "ün" => "ㄩㄣ",
+            rime => rime,
+        };
+        match syllable.tone() {
+            Some(5) => format!("{}{initial}{rime}", Self::NEUTRAL),
+            Some(tone @ 2..=4) => format!("{initial}{rime}{}", Self::TONES[tone as usize - 1]),
+            _ => format!("{initial}{rime}"),
+        }
+    }
+
+    /// 按注音的习惯, 没有声调符号的音节为一声
+    fn decode_all(&self, text: &str) -> Vec<Syllable> {
+        static TABLE: OnceLock<ReverseTable> = OnceLock::new();
+        let table = TABLE.get_or_init(|| reverse_table(self, &[None], str::to_string));
+
+        let text = text.trim();
+        if let Some(body) = text.strip_prefix(Self::NEUTRAL).or_else(|| text.strip_suffix(Self::NEUTRAL)) {
+            return lookup(table, body, Some(5));
+        }
+        match text.chars().last().and_then(|c| Self::TONES.iter().position(|&mark| mark == c).map(|idx| (c, idx))) {
+            Some((c, idx)) => lookup(table, &text[..text.len() - c.len_utf8()], Some(idx as u8 + 1)),
+            None => lookup(table, text, Some(1)),
+        }
+    }
+}
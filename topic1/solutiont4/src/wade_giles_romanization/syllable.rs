@@ -0,0 +1,233 @@
+//! 以汉语拼音为基准的音节表示
+//!
+//! 音节拆分为声母、韵母与声调, 各种拼写方案都从这一表示出发进行拼写, 也都解析回这一表示
+//! - 韵母采用完整形式: 拼音的 iu、ui、un 记为 iou、uei、uen, j q x 之后的 u 记为 ü,
+//!   零声母的 y w 并入韵母 (yi → i, wu → u, yu → ü), zhi chi shi ri zi ci si 的韵母记为 -i
+//! - 声调为 1..=4, 轻声为 5, None 表示未标注声调
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use unicode_normalization::UnicodeNormalization;
+
+/// 声母, 双字母的排在前面以便按前缀匹配
+pub const INITIALS: [&str; 21] = [
+    "zh", "ch", "sh", "b", "p", "m", "f", "d", "t", "n", "l", "g", "k", "h", "j", "q", "x", "r", "z", "c", "s",
+];
+
+pub const FINALS: [&str; 40] = [
+    "a", "o", "e", "ê", "ai", "ei", "ao", "ou", "an", "en", "ang", "eng", "ong", "er", "-i",
+    "i", "ia", "io", "ie", "iai", "iao", "iou", "ian", "in", "iang", "ing", "iong",
+    "u", "ua", "uo", "uai", "uei", "uan", "uen", "uang", "ueng",
+    "ü", "üe", "üan", "ün",
+];
+
+/// 普通话中实际存在的音节 (不计声调), 按《通用规范汉字表》中取该读音的字数从多到少排列,
+/// 同一拼写对应多个音节时排在前面的更常用
+pub const SYLLABLES: [&str; 413] = [
+    "yi", "ji", "yu", "xi", "zhi", "fu", "li", "qi", "yan", "shi", "wei", "jian", "ju", "bi", "wu",
+    "xian", "jiao", "lu", "qian", "zhu", "hu", "shu", "jie", "you", "di", "gu", "pi", "jing", "yin",
+    "hui", "xie", "chi", "xu", "qu", "jin", "ying", "zhen", "jia", "bo", "yao", "han", "zi", "xuan",
+    "xiao", "yuan", "chu", "huan", "xun", "chan", "he", "e", "gui", "shan", "cheng", "shen", "ke",
+    "jue", "ling", "si", "yun", "mo", "fei", "ge", "kui", "ya", "huang", "yong", "lin", "ba", "mi",
+    "ti", "dan", "yang", "wan", "zha", "xiang", "lian", "tan", "mao", "bei", "tang", "tong", "pu",
+    "fan", "mei", "su", "qiu", "liu", "bian", "gan", "xing", "chang", "du", "jiu", "qiao", "huo",
+    "quan", "dian", "ao", "liao", "ye", "hong", "zhan", "zhou", "luo", "zhuo", "cha", "guan",
+    "ting", "ban", "ni", "tuo", "sui", "ci", "feng", "jiang", "qing", "zhang", "meng", "zhe", "lan",
+    "fen", "ai", "bao", "lü", "biao", "gou", "lei", "qin", "duo", "hao", "wen", "da", "dai", "lang",
+    "tu", "chen", "chou", "gong", "lao", "qiang", "mu", "xiu", "zheng", "an", "peng", "tao", "jun",
+    "ru", "she", "ping", "ta", "xia", "long", "yue", "die", "bu", "dong", "man", "gao", "zhao",
+    "dao", "liang", "shao", "tian", "guo", "po", "zhong", "xin", "min", "wo", "lou", "mian", "hou",
+    "ren", "zao", "ding", "bang", "fang", "kuang", "song", "cuo", "dun", "nie", "sha", "dang",
+    "kun", "bin", "kan", "pei", "gang", "rong", "lie", "suo", "juan", "cui", "sheng", "chong", "er",
+    "la", "sou", "tiao", "ma", "pan", "tai", "diao", "nao", "wang", "zuo", "geng", "kai", "miao",
+    "cong", "hua", "can", "bing", "lai", "mang", "chun", "chao", "ou", "ming", "zong", "ze", "na",
+    "que", "ku", "hai", "nian", "gai", "gua", "deng", "shou", "pian", "shang", "dou", "xue", "zu",
+    "zhuan", "wa", "pao", "chuan", "kang", "piao", "pang", "cai", "duan", "ben", "hun", "qie",
+    "beng", "cu", "zan", "nan", "luan", "ning", "bai", "jiong", "nai", "guang", "pin", "kuai", "pa",
+    "qiong", "sa", "tun", "xiong", "fa", "kou", "lun", "mai", "zai", "cao", "pai", "heng", "chui",
+    "rang", "kao", "zeng", "nu", "che", "tui", "zhui", "sao", "rui", "zou", "mou", "shuo", "kong",
+    "le", "zun", "ga", "dui", "se", "ran", "nuo", "chuang", "weng", "teng", "chai", "chuo", "nong",
+    "zhuang", "men", "sun", "san", "zhai", "za", "tuan", "hang", "shuang", "gun", "niu", "cang",
+    "zang", "kua", "cuan", "ruo", "leng", "zui", "bie", "qia", "tou", "ce", "rou", "huai", "nang",
+    "sang", "ken", "cun", "rao", "gen", "zhun", "te", "mie", "ruan", "kuo", "niao", "shui", "guai",
+    "de", "tie", "lüe", "sai", "zuan", "shuai", "ceng", "pie", "chuai", "shuan", "hen", "ka",
+    "keng", "ang", "pou", "qun", "suan", "nü", "shun", "a", "wai", "kuan", "shai", "cen", "cou",
+    "en", "pen", "hei", "shua", "ha", "o", "nei", "reng", "fou", "ca", "run", "ne", "zei", "ri",
+    "nen", "niang", "zhua", "nüe", "re", "diu", "zen", "yo", "nou", "chua", "neng", "nin", "nuan",
+    "sen", "me", "seng", "den", "miu", "zhuai", "gei", "lia", "fo", "biang", "ei", "cei", "din",
+    "eng", "fiao", "kei", "lo", "nun", "rua", "shei", "yai",
+];
+
+/// 拼音声调符号 (组合字符), 下标为声调减 1
+const TONE_MARKS: [char; 4] = ['\u{304}', '\u{301}', '\u{30C}', '\u{300}'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Syllable {
+    initial: &'static str,
+    rime: &'static str,
+    tone: Option<u8>,
+}
+
+impl Syllable {
+    /// 解析一个拼音音节, 声调可以用符号 (zhōng) 或数字 (zhong1) 表示, ü 也可以写作 v 或 u:
+    pub fn from_pinyin(text: &str) -> Option<Self> {
+        let mut text = text.trim().to_lowercase();
+        if text.contains('v') || text.contains("u:") {
+            text = text.replace("u:", "ü").replace('v', "ü");
+        }
+        let (text, tone) = match text.chars().last()? {
+            digit @ '1'..='5' => (text[..text.len() - 1].to_string(), digit.to_digit(10).map(|d| d as u8)),
+            _ => strip_tone_marks(&text),
+        };
+        Self::parse_toneless(&text).map(|syllable| syllable.with_tone(tone))
+    }
+
+    fn parse_toneless(text: &str) -> Option<Self> {
+        static INVENTORY: OnceLock<HashMap<&str, Syllable>> = OnceLock::new();
+        let inventory = INVENTORY.get_or_init(|| {
+            SYLLABLES.iter().map(|&text| (text, Self::split(text).unwrap())).collect()
+        });
+        inventory.get(text).copied()
+    }
+
+    /// 按拼写规则拆分出声母与韵母, 不检查音节是否存在
+    fn split(text: &str) -> Option<Self> {
+        let (initial, rest) = match INITIALS.iter().find(|initial| text.starts_with(*initial)) {
+            Some(&initial) => (initial, &text[initial.len()..]),
+            None => ("", text),
+        };
+
+        let rime = if let Some(rest) = rest.strip_prefix('y') {
+            match rest {
+                _ if rest.starts_with('u') => format!("ü{}", &rest[1..]),
+                _ if rest.starts_with('i') => rest.to_string(),
+                _ => format!("i{rest}"),
+            }
+        } else if let Some(rest) = rest.strip_prefix('w') {
+            format!("u{}", rest.strip_prefix('u').unwrap_or(rest))
+        } else {
+            match (initial, rest) {
+                ("j" | "q" | "x", _) if rest.starts_with('u') => format!("ü{}", &rest[1..]),
+                ("zh" | "ch" | "sh" | "r" | "z" | "c" | "s", "i") => "-i".to_string(),
+                (_, "iu") => "iou".to_string(),
+                (_, "ui") => "uei".to_string(),
+                (_, "un") => "uen".to_string(),
+                _ => rest.to_string(),
+            }
+        };
+        let &rime = FINALS.iter().find(|&&f| f == rime)?;
+        Some(Syllable { initial, rime, tone: None })
+    }
+
+    /// 不计声调的全部音节, 顺序同 SYLLABLES
+    pub fn all() -> impl Iterator<Item = Syllable> {
+        SYLLABLES.iter().map(|text| Self::parse_toneless(text).unwrap())
+    }
+
+    /// 拼音声母, 零声母为 ""
+    pub fn initial(&self) -> &'static str {
+        self.initial
+    }
+
+    /// 完整形式的韵母, 见模块说明
+    pub fn rime(&self) -> &'static str {
+        self.rime
+    }
+
+    pub fn tone(&self) -> Option<u8> {
+        self.tone
+    }
+
+    pub fn with_tone(self, tone: Option<u8>) -> Self {
+        Syllable { tone: tone.filter(|tone| (1..=5).contains(tone)), ..self }
+    }
+
+    /// 不带声调的拼音拼写
+    pub fn pinyin(&self) -> String {
+        let rime = self.rime;
+        if self.initial.is_empty() {
+            return match rime {
+                "i" | "in" | "ing" => format!("y{rime}"),
+                "u" => "wu".to_string(),
+                _ if rime.starts_with('ü') => format!("yu{}", &rime['ü'.len_utf8()..]),
+                _ if rime.starts_with('i') => format!("y{}", &rime[1..]),
+                _ if rime.starts_with('u') => format!("w{}", &rime[1..]),
+                _ => rime.to_string(),
+            };
+        }
+        let rime = match (self.initial, rime) {
+            (_, "-i") => "i".to_string(),
+            (_, "iou") => "iu".to_string(),
+            (_, "uei") => "ui".to_string(),
+            (_, "uen") => "un".to_string(),
+            ("j" | "q" | "x", _) => rime.replace('ü', "u"),
+            _ => rime.to_string(),
+        };
+        format!("{}{rime}", self.initial)
+    }
+}
+
+/// 按拼音的规则为 text 标上声调符号: 有 a 或 e 时标在其上, ou 标在 o 上, 否则标在最后一个元音上;
+/// 没有元音时标在最后一个字母上, 轻声与未标注的声调不加符号
+pub fn place_tone_mark(text: &str, tone: Option<u8>) -> String {
+    let Some(mark) = tone.and_then(|tone| TONE_MARKS.get(tone as usize - 1)) else {
+        return text.to_string();
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'ü' | 'ê');
+    let position = chars.iter().position(|&c| c == 'a' || c == 'e' || c == 'ê')
+        .or_else(|| text.find("ou").map(|_| chars.iter().position(|&c| c == 'o').unwrap()))
+        .or_else(|| chars.iter().rposition(|&c| is_vowel(c)))
+        .unwrap_or(chars.len().saturating_sub(1));
+
+    let mut res = String::with_capacity(text.len() + 2);
+    for (idx, &c) in chars.iter().enumerate() {
+        res.push(c);
+        if idx == position {
+            res.push(*mark);
+        }
+    }
+    res.nfc().collect()
+}
+
+/// 去掉声调符号, 返回 (去掉符号后的文本, 声调), 没有声调符号时声调为 None
+pub fn strip_tone_marks(text: &str) -> (String, Option<u8>) {
+    let mut tone = None;
+    // 常见的预组合字符直接查表, 其余情况分解后去掉组合符号
+    if text.chars().all(|c| c.is_ascii() || c == 'ü' || toned_vowel(c).is_some()) {
+        let stripped = text.chars()
+            .map(|c| match toned_vowel(c) {
+                Some((base, t)) => {
+                    tone = Some(t);
+                    base
+                }
+                None => c,
+            })
+            .collect();
+        return (stripped, tone);
+    }
+
+    let stripped: String = text.nfd()
+        .filter(|c| match TONE_MARKS.iter().position(|mark| mark == c) {
+            Some(idx) => {
+                tone = Some(idx as u8 + 1);
+                false
+            }
+            None => true,
+        })
+        .collect();
+    (stripped.nfc().collect(), tone)
+}
+
+/// 带声调符号的预组合元音, 返回 (元音, 声调)
+fn toned_vowel(c: char) -> Option<(char, u8)> {
+    let (base, tone) = match c {
+        'ā' => ('a', 1), 'á' => ('a', 2), 'ǎ' => ('a', 3), 'à' => ('a', 4),
+        'ē' => ('e', 1), 'é' => ('e', 2), 'ě' => ('e', 3), 'è' => ('e', 4),
+        'ī' => ('i', 1), 'í' => ('i', 2), 'ǐ' => ('i', 3), 'ì' => ('i', 4),
+        'ō' => ('o', 1), 'ó' => ('o', 2), 'ǒ' => ('o', 3), 'ò' => ('o', 4),
+        'ū' => ('u', 1), 'ú' => ('u', 2), 'ǔ' => ('u', 3), 'ù' => ('u', 4),
+        'ǖ' => ('ü', 1), 'ǘ' => ('ü', 2), 'ǚ' => ('ü', 3), 'ǜ' => ('ü', 4),
+        _ => return None,
+    };
+    Some((base, tone))
+}
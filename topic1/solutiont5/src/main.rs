@@ -2,4 +2,13 @@ mod retirement;
 fn main() {
     let res = retirement::retire_time("1971-04", "原法定退休年龄55周岁女职工"); // 2026-08,55.33,4
     println!("{res}");
+
+    match retirement::flexible_retire_time("1970-01", "男职工", "2030-01", "2000-01..2029-12") {
+        Ok(res) => println!("{res}"),
+        Err(reason) => println!("{reason}"),
+    }
+
+    for policy in &retirement::rules::RuleSet::shared().policies {
+        println!("{} ({})", policy.name, policy.effective);
+    }
 }
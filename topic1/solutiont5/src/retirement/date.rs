@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct Date {
     pub year: isize,
     pub month: isize,
@@ -37,8 +37,26 @@ impl Date {
         }
     }
 
-    pub fn to_month(&self) -> usize {
-        (self.year * 12 + self.month) as usize 
+    /// 以月为单位的序号, 用于比较年月或把一段时间折算为月数
+    pub fn to_month(&self) -> isize {
+        self.year * 12 + self.month
+    }
+
+    /// 解析 "YYYY-MM" 形式的年月, 格式或月份有误时为 None
+    pub fn parse_month(text: &str) -> Option<Date> {
+        let (year, month) = text.trim().split_once('-')?;
+        let date = Date::new(year.parse().ok()?, month.parse().ok()?);
+        (1..=12).contains(&date.month).then_some(date)
+    }
+
+    /// 由 to_month 的结果还原年月 (月份为 1..=12)
+    pub fn from_month_index(index: isize) -> Date {
+        Date::new((index - 1).div_euclid(12), (index - 1).rem_euclid(12) + 1)
+    }
+
+    /// 月数折算为一段时间 (月份为 0..12)
+    pub fn from_months(months: isize) -> Date {
+        Date::new_abs(months.div_euclid(12), months.rem_euclid(12))
     }
 }
 
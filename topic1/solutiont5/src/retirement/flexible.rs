@@ -0,0 +1,195 @@
+//! 弹性提前/延迟退休与最低缴费年限的校验
+//!
+//! # 参考文献:
+//! - 《国务院关于渐进式延迟法定退休年龄的办法》第二条、第三条
+//!
+//! # 主要特点:
+//! - 达到最低缴费年限的职工可以自愿弹性提前退休, 提前时间最长不超过三年, 且退休年龄不得低于原法定退休年龄
+//! - 达到法定退休年龄后, 与单位协商一致的可以弹性延迟退休, 延迟时间最长不超过三年
+//! - 最低缴费年限自 2030 年 1 月起每年提高六个月, 由十五年逐步提高至二十年;
+//!   按时或延迟退休时不满最低缴费年限的, 可以延长缴费或一次性缴费补足, 不影响退休
+//! - 可提前/延迟的月数、最低缴费年限均来自 RuleSet 的配置
+use std::fmt;
+use std::io;
+use std::io::ErrorKind;
+use std::str::FromStr;
+use crate::retirement::date::Date;
+use crate::retirement::rules::{RetirementRules, RuleSet};
+use crate::retirement::types::PersonnelCategory;
+
+/// 缴费记录, 每一段为首尾都包含在内的年月区间
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContributionHistory {
+    periods: Vec<(Date, Date)>,
+}
+
+impl ContributionHistory {
+    /// 在 date 之前 (不含 date 当月) 缴费的月数, 重叠的月份只计一次
+    pub fn months_before(&self, date: &Date) -> isize {
+        let mut periods: Vec<(isize, isize)> = self.periods.iter()
+            .map(|(start, end)| (start.to_month(), end.to_month().min(date.to_month() - 1)))
+            .filter(|(start, end)| start <= end)
+            .collect();
+        periods.sort_unstable();
+
+        let mut total = 0;
+        let mut counted_until = isize::MIN;
+        for (start, end) in periods {
+            let start = start.max(counted_until + 1);
+            if start <= end {
+                total += end - start + 1;
+                counted_until = end;
+            }
+        }
+        total
+    }
+}
+
+/// "2000-01..2010-12,2012-03..2026-05" 形式的缴费记录, 空字符串表示没有缴费
+impl FromStr for ContributionHistory {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |text: &str| io::Error::new(ErrorKind::InvalidData, format!("无法识别的缴费区间: {text}"));
+        let periods = s.split(',')
+            .map(str::trim)
+            .filter(|period| !period.is_empty())
+            .map(|period| {
+                let (start, end) = period.split_once("..").ok_or_else(|| invalid(period))?;
+                let start = Date::parse_month(start).ok_or_else(|| invalid(period))?;
+                let end = Date::parse_month(end).ok_or_else(|| invalid(period))?;
+                if end < start {
+                    return Err(invalid(period));
+                }
+                Ok((start, end))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(ContributionHistory { periods })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetirementKind {
+    Early,      // 弹性提前退休
+    Statutory,  // 按法定退休年龄退休
+    Late,       // 弹性延迟退休
+}
+
+/// 校验通过的退休安排
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetirementPlan {
+    /// 法定退休年月
+    pub statutory: Date,
+    /// 实际退休年月
+    pub retire: Date,
+    /// 实际退休年龄
+    pub age: Date,
+    pub kind: RetirementKind,
+    /// 相对法定退休年月的月数, 提前为负
+    pub adjustment: isize,
+    /// 退休时的最低缴费月数
+    pub minimum_contribution: isize,
+    /// 退休前已缴费的月数
+    pub contributed: isize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetirementRejection {
+    NoRules,                                                                // 没有适用于该人员类别的退休规则
+    NotAvailable { requested: Date, statutory: Date },                      // 弹性退休尚未实施或未配置
+    BeforeOriginalAge { requested: Date, original: Date },                  // 早于原法定退休年龄
+    TooEarly { requested: Date, earliest: Date, max_early: isize },         // 提前超过允许的月数
+    TooLate { requested: Date, latest: Date, max_late: isize },             // 延迟超过允许的月数
+    InsufficientContribution { requested: Date, required: isize, contributed: isize }, // 提前退休时未达到最低缴费年限
+}
+
+impl fmt::Display for RetirementRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetirementRejection::NoRules => write!(f, "没有适用于该人员类别的退休规则"),
+            RetirementRejection::NotAvailable { requested, statutory } =>
+                write!(f, "{requested} 不能弹性退休, 只能在法定退休时间 {statutory} 退休"),
+            RetirementRejection::BeforeOriginalAge { requested, original } =>
+                write!(f, "{requested} 早于原法定退休年龄对应的 {original}, 提前退休不得低于原法定退休年龄"),
+            RetirementRejection::TooEarly { requested, earliest, max_early } =>
+                write!(f, "{requested} 早于最早可以退休的 {earliest}, 提前退休最长不超过{}", duration(*max_early)),
+            RetirementRejection::TooLate { requested, latest, max_late } =>
+                write!(f, "{requested} 晚于最晚可以退休的 {latest}, 延迟退休最长不超过{}", duration(*max_late)),
+            RetirementRejection::InsufficientContribution { requested, required, contributed } =>
+                write!(f, "{requested} 退休的最低缴费年限为{}, 已缴费{}, 未达到最低缴费年限不能提前退休",
+                       duration(*required), duration(*contributed)),
+        }
+    }
+}
+
+impl std::error::Error for RetirementRejection { }
+
+/// 校验在 requested 退休是否符合弹性退休的时间范围与最低缴费年限
+pub fn plan_retirement(
+    rules: &RuleSet,
+    birth: &Date,
+    types: &PersonnelCategory,
+    requested: &Date,
+    history: &ContributionHistory,
+) -> Result<RetirementPlan, RetirementRejection> {
+    let working = rules.calculate_working_date(birth, types).ok_or(RetirementRejection::NoRules)?;
+    let original = rules.baseline().calculate_working_date(birth, types).ok_or(RetirementRejection::NoRules)?;
+    let statutory = Date::from_month_index(birth.to_month() + working.to_month());
+    let original = Date::from_month_index(birth.to_month() + original.to_month());
+
+    let adjustment = requested.to_month() - statutory.to_month();
+    let minimum_contribution = rules.minimum_contribution(requested);
+    let contributed = history.months_before(requested);
+    let kind = match adjustment {
+        0 => RetirementKind::Statutory,
+        ..0 => RetirementKind::Early,
+        _ => RetirementKind::Late,
+    };
+
+    if kind != RetirementKind::Statutory {
+        let not_available = RetirementRejection::NotAvailable { requested: *requested, statutory };
+        let (policy, flexible) = rules.flexible().ok_or(not_available.clone())?;
+        if *requested < policy.effective {
+            return Err(not_available);
+        }
+
+        if kind == RetirementKind::Early {
+            if *requested < original {
+                return Err(RetirementRejection::BeforeOriginalAge { requested: *requested, original });
+            }
+            if -adjustment > flexible.max_early {
+                let earliest = Date::from_month_index(statutory.to_month() - flexible.max_early);
+                return Err(RetirementRejection::TooEarly { requested: *requested, earliest, max_early: flexible.max_early });
+            }
+            if contributed < minimum_contribution {
+                return Err(RetirementRejection::InsufficientContribution {
+                    requested: *requested,
+                    required: minimum_contribution,
+                    contributed,
+                });
+            }
+        } else if adjustment > flexible.max_late {
+            let latest = Date::from_month_index(statutory.to_month() + flexible.max_late);
+            return Err(RetirementRejection::TooLate { requested: *requested, latest, max_late: flexible.max_late });
+        }
+    }
+
+    Ok(RetirementPlan {
+        statutory,
+        retire: *requested,
+        age: Date::from_months(requested.to_month() - birth.to_month()),
+        kind,
+        adjustment,
+        minimum_contribution,
+        contributed,
+    })
+}
+
+/// 月数的中文表示, 如 "15年6个月"
+fn duration(months: isize) -> String {
+    match (months / 12, months % 12) {
+        (years, 0) => format!("{years}年"),
+        (0, months) => format!("{months}个月"),
+        (years, months) => format!("{years}年{months}个月"),
+    }
+}
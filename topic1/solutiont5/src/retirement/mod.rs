@@ -6,25 +6,25 @@
     - 法定退休年龄**每二个月延迟一个月**，**逐步延迟至五十五周岁**。
 */
 use crate::retirement::date::Date;
-use crate::retirement::rules::{CombinedRules, RetirementRules};
+use crate::retirement::flexible::ContributionHistory;
+use crate::retirement::rules::{RetirementRules, RuleSet};
 use crate::retirement::types::PersonnelCategory;
 
-mod date;
-mod types;
-mod rules;
+pub(crate) mod date;
+pub(crate) mod flexible;
+pub(crate) mod types;
+pub(crate) mod rules;
 
 pub fn retire_time(time: &str, tp: &str) -> String {
     let (year, month) = time.split_once('-').unwrap();
     let types: PersonnelCategory = tp.parse().unwrap();
     let birth_date = Date::new(year.parse().unwrap(), month.parse().unwrap());
-    // dbg!(&year, &month, &types);
 
-    /// 当前使用的规则
-    /// 传入的顺序决定了不同规则适用的先后
-    let retire_rules = CombinedRules { rules: vec![Box::new(rules::Rules20240913), Box::new(rules::Rules1978) ] } ;
+    // 当前使用的规则由配置文件给出, 按生效日期依次叠加
+    let retire_rules = RuleSet::shared();
     let mixed_working_time = retire_rules.calculate_working_date(&birth_date, &types);
-    let original_retire_time = rules::Rules1978.calculate_working_date(&birth_date, &types);
-    
+    let original_retire_time = retire_rules.baseline().calculate_working_date(&birth_date, &types);
+
     let retire_delay = mixed_working_time.unwrap() - original_retire_time.unwrap();
 
     format!("{},{},{}",
@@ -34,6 +34,19 @@ pub fn retire_time(time: &str, tp: &str) -> String {
     )
 }
 
+/// 弹性退休: 在 retire_time 的输入之外给出申请的退休年月 (YYYY-MM) 与缴费记录 ("2000-01..2024-12,..."),
+/// 符合条件时输出 "退休时间,退休年龄,相对法定退休时间的月数 (提前为负)", 否则返回不符合的原因
+pub fn flexible_retire_time(time: &str, tp: &str, requested: &str, contributions: &str) -> Result<String, String> {
+    let birth_date = Date::parse_month(time).ok_or_else(|| format!("无法识别的出生年月: {time}"))?;
+    let types: PersonnelCategory = tp.parse().map_err(|e: std::io::Error| e.to_string())?;
+    let requested = Date::parse_month(requested).ok_or_else(|| format!("无法识别的退休年月: {requested}"))?;
+    let history: ContributionHistory = contributions.parse().map_err(|e: std::io::Error| e.to_string())?;
+
+    let plan = flexible::plan_retirement(RuleSet::shared(), &birth_date, &types, &requested, &history)
+        .map_err(|e| e.to_string())?;
+    Ok(format!("{},{},{}", plan.retire, format_f32(plan.age.into()), plan.adjustment))
+}
+
 fn format_f32(value: f32) -> String {
     if value.fract() == 0.0 {
         format!("{}", value)
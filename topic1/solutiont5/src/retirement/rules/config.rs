@@ -0,0 +1,301 @@
+//! 由配置文件 (TOML) 描述的退休政策
+//!
+//! # 主要特点:
+//! - 每项政策按人员类别给出退休年龄, 或按出生批次逐步延迟的规则 (开始/结束批次、每几个月延迟一个月、最多延迟的月数)
+//! - 政策按生效日期排列, RuleSet 依次叠加全部政策, 第一项政策作为计算延迟月数的基准
+//! - 政策还可以给出弹性退休可提前/延迟的月数, 以及按退休年月逐步提高的最低缴费年限
+//! - 政策调整或地区差异只需修改配置文件; 设置环境变量 RETIREMENT_RULES 为配置文件路径即可在运行时替换内置的配置
+//!
+//! # 配置格式:
+//! ```toml
+//! [[policy]]
+//! name = "关于实施渐进式延迟法定退休年龄的决定"
+//! effective = "2025-01"
+//!
+//! [policy.categories."男职工"]
+//! start = "1965-01"
+//! step = 4
+//! max_delay = 36
+//! ```
+use std::collections::HashMap;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::OnceLock;
+use serde::Deserialize;
+use crate::retirement::date::Date;
+use crate::retirement::rules::RetirementRules;
+use crate::retirement::types::PersonnelCategory;
+
+/// 内置的配置, 与 retirement_rules.toml 相同
+const BUILTIN_RULES: &str = include_str!("../../../retirement_rules.toml");
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    policy: Vec<PolicyConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyConfig {
+    name: String,
+    effective: String,
+    #[serde(default)]
+    categories: HashMap<String, CategoryConfig>,
+    flexible: Option<Flexible>,
+    minimum_contribution: Option<ContributionConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CategoryConfig {
+    base_age: Option<isize>,
+    #[serde(default)]
+    base_age_months: isize,
+    start: Option<String>,
+    end: Option<String>,
+    step: Option<isize>,
+    #[serde(default = "default_delay_per_step")]
+    delay_per_step: isize,
+    max_delay: Option<isize>,
+}
+
+fn default_delay_per_step() -> isize {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContributionConfig {
+    base: isize,
+    start: Option<String>,
+    step: Option<isize>,
+    increase_per_step: Option<isize>,
+    max_increase: Option<isize>,
+}
+
+/// 弹性退休: 达到最低缴费年限时最多可提前的月数, 以及与单位协商一致时最多可延迟的月数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Flexible {
+    pub max_early: isize,
+    pub max_late: isize,
+}
+
+/// 最低缴费年限的月数, 按退休年月逐步提高
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinimumContribution {
+    pub base: isize,
+    pub increase: Option<PhaseIn>,
+}
+
+impl MinimumContribution {
+    /// 在 date 退休时的最低缴费月数
+    pub fn at(&self, date: &Date) -> isize {
+        self.base + self.increase.as_ref().map_or(0, |increase| increase.delay(date))
+    }
+}
+
+/// 按出生批次 (或退休年月) 逐步延迟的规则, 月份均以月数表示
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseIn {
+    pub start: Date,
+    pub end: Option<Date>,
+    pub step: isize,
+    pub delay_per_step: isize,
+    pub max_delay: isize,
+}
+
+impl PhaseIn {
+    /// 出生于 birth 的人延迟的月数
+    pub fn delay(&self, birth: &Date) -> isize {
+        let since_start = birth.to_month() - self.start.to_month();
+        if since_start < 0 {
+            return 0;
+        }
+        if self.end.is_some_and(|end| birth.to_month() >= end.to_month()) {
+            return self.max_delay;
+        }
+        ((since_start / self.step + 1) * self.delay_per_step).min(self.max_delay)
+    }
+}
+
+/// 一个人员类别在一项政策中的规则
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryRule {
+    /// 退休年龄的月数
+    pub base_age: Option<isize>,
+    pub phase_in: Option<PhaseIn>,
+}
+
+/// 由配置文件中的一项政策构成的 RetirementRules
+#[derive(Debug, Clone)]
+pub struct ConfigRules {
+    pub name: String,
+    pub effective: Date,
+    pub flexible: Option<Flexible>,
+    pub minimum_contribution: Option<MinimumContribution>,
+    categories: HashMap<PersonnelCategory, CategoryRule>,
+}
+
+impl RetirementRules for ConfigRules {
+    fn calculate_working_date(&self, date: &Date, types: &PersonnelCategory) -> Option<Date> {
+        let rule = self.categories.get(types)?;
+        let delay = rule.phase_in.as_ref().map_or(0, |phase_in| phase_in.delay(date));
+        match (rule.base_age, delay) {
+            (None, 0) => None,
+            (base_age, delay) => Some(Date::from_months(base_age.unwrap_or(0) + delay)),
+        }
+    }
+}
+
+/// 按生效日期排列的全部政策
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    pub policies: Vec<ConfigRules>,
+}
+
+impl RuleSet {
+    /// 解析 TOML 格式的配置, 配置有误时返回 InvalidData 并说明原因
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let file: RuleFile = toml::from_str(text).map_err(|e| invalid(e.to_string()))?;
+        let mut policies = file.policy.into_iter().map(ConfigRules::try_from).collect::<io::Result<Vec<_>>>()?;
+        if policies.is_empty() {
+            return Err(invalid("no policy is defined".into()));
+        }
+        policies.sort_by_key(|policy| policy.effective.to_month());
+        Ok(RuleSet { policies })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// 内置的配置
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_RULES).expect("builtin retirement rules are well-formed")
+    }
+
+    /// 进程内共享的配置: 环境变量 RETIREMENT_RULES 指定的文件, 未设置时为内置的配置
+    pub fn shared() -> &'static RuleSet {
+        static SHARED: OnceLock<RuleSet> = OnceLock::new();
+        SHARED.get_or_init(|| match std::env::var_os("RETIREMENT_RULES") {
+            Some(path) => Self::load(&path).unwrap_or_else(|e| panic!("failed to load {path:?}: {e}")),
+            None => Self::builtin(),
+        })
+    }
+
+    /// 计算延迟月数的基准, 即最早生效的政策
+    pub fn baseline(&self) -> &ConfigRules {
+        &self.policies[0]
+    }
+
+    /// 最晚生效的给出了弹性退休规则的政策
+    pub fn flexible(&self) -> Option<(&ConfigRules, Flexible)> {
+        self.policies.iter().rev().find_map(|policy| Some((policy, policy.flexible?)))
+    }
+
+    /// 在 date 退休时的最低缴费月数, 取最晚生效的给出了最低缴费年限的政策; 都没有给出时为 0
+    pub fn minimum_contribution(&self, date: &Date) -> isize {
+        self.policies.iter()
+            .rev()
+            .find_map(|policy| policy.minimum_contribution.as_ref())
+            .map_or(0, |minimum| minimum.at(date))
+    }
+
+}
+
+impl RetirementRules for RuleSet {
+    fn calculate_working_date(&self, date: &Date, types: &PersonnelCategory) -> Option<Date> {
+        let months = self.policies.iter()
+            .filter_map(|policy| policy.calculate_working_date(date, types))
+            .map(|working_date| working_date.to_month())
+            .reduce(|total, months| total + months)?;
+        Some(Date::from_months(months))
+    }
+}
+
+impl TryFrom<PolicyConfig> for ConfigRules {
+    type Error = io::Error;
+
+    fn try_from(config: PolicyConfig) -> Result<Self, Self::Error> {
+        let context = |e: io::Error| invalid(format!("policy '{}': {e}", config.name));
+        let effective = parse_date(&config.effective).map_err(context)?;
+        let mut categories = HashMap::new();
+        for (name, category) in &config.categories {
+            let types: PersonnelCategory = name.parse().map_err(|_| context(invalid(format!("unknown category '{name}'"))))?;
+            let rule = CategoryRule::try_from(category)
+                .map_err(|e| context(invalid(format!("category '{name}': {e}"))))?;
+            categories.insert(types, rule);
+        }
+        if config.flexible.is_some_and(|flexible| flexible.max_early < 0 || flexible.max_late < 0) {
+            return Err(context(invalid("max_early and max_late must not be negative".into())));
+        }
+        let minimum_contribution = config.minimum_contribution.as_ref()
+            .map(MinimumContribution::try_from)
+            .transpose()
+            .map_err(|e| context(invalid(format!("minimum_contribution: {e}"))))?;
+        Ok(ConfigRules { name: config.name, effective, flexible: config.flexible, minimum_contribution, categories })
+    }
+}
+
+impl TryFrom<&CategoryConfig> for CategoryRule {
+    type Error = io::Error;
+
+    fn try_from(config: &CategoryConfig) -> Result<Self, Self::Error> {
+        let base_age = config.base_age.map(|years| years * 12 + config.base_age_months);
+        if base_age.is_some_and(|months| months <= 0) || !(0..12).contains(&config.base_age_months) {
+            return Err(invalid("base_age must be positive and base_age_months in 0..12".into()));
+        }
+
+        let phase_in = phase_in(config.start.as_deref(), config.end.as_deref(), config.step, config.delay_per_step, config.max_delay)
+            .map_err(|e| invalid(format!("{e} (start, step, delay_per_step, max_delay)")))?;
+
+        if base_age.is_none() && phase_in.is_none() {
+            return Err(invalid("neither base_age nor a phase-in is given".into()));
+        }
+        Ok(CategoryRule { base_age, phase_in })
+    }
+}
+
+impl TryFrom<&ContributionConfig> for MinimumContribution {
+    type Error = io::Error;
+
+    fn try_from(config: &ContributionConfig) -> Result<Self, Self::Error> {
+        if config.base < 0 {
+            return Err(invalid("base must not be negative".into()));
+        }
+        let increase = phase_in(config.start.as_deref(), None, config.step, config.increase_per_step.unwrap_or(1), config.max_increase)
+            .map_err(|e| invalid(format!("{e} (start, step, increase_per_step, max_increase)")))?;
+        Ok(MinimumContribution { base: config.base, increase })
+    }
+}
+
+/// 校验并构造逐步延迟的规则, 开始时间、步长与上限都没有给出时为 None
+fn phase_in(start: Option<&str>, end: Option<&str>, step: Option<isize>, per_step: isize, max: Option<isize>) -> io::Result<Option<PhaseIn>> {
+    match (start, step, max) {
+        (None, None, None) if end.is_none() => Ok(None),
+        (Some(start), Some(step), Some(max)) => {
+            if step <= 0 || per_step <= 0 || max < 0 {
+                return Err(invalid("steps must be positive and the maximum must not be negative".into()));
+            }
+            let start = parse_date(start)?;
+            let end = end.map(parse_date).transpose()?;
+            if end.is_some_and(|end| end.to_month() < start.to_month()) {
+                return Err(invalid("end is before start".into()));
+            }
+            Ok(Some(PhaseIn { start, end, step, delay_per_step: per_step, max_delay: max }))
+        }
+        _ => Err(invalid("start, step and maximum must be given together".into())),
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+/// 解析 "YYYY-MM" 形式的年月
+fn parse_date(text: &str) -> io::Result<Date> {
+    Date::parse_month(text).ok_or_else(|| invalid(format!("'{text}' is not a YYYY-MM date")))
+}
@@ -1,7 +1,7 @@
 use std::io::ErrorKind;
 use std::str::FromStr;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum PersonnelCategory {
     Man,
     FemaleCadres,
@@ -37,4 +37,128 @@ mod tests {
         println!("Total score: {:.2}", total_score);
         assert_eq!(100.00, total_score);
     }
+
+    #[test]
+    fn test_config_rules() {
+        use super::retirement::date::Date;
+        use super::retirement::rules::{RetirementRules, RuleSet};
+        use super::retirement::types::PersonnelCategory;
+
+        // 内置的配置: 出生年月、人员类别与退休年龄的月数, 原法定退休年龄分别为 60、55、50 周岁
+        let rule_set = RuleSet::builtin();
+        let months = |date: Option<Date>| date.map(|date| date.year * 12 + date.month);
+        let expected = [
+            ((1964, 12), PersonnelCategory::Man, 720),
+            ((1965, 1), PersonnelCategory::Man, 721),
+            ((1965, 5), PersonnelCategory::Man, 722),
+            ((1970, 1), PersonnelCategory::Man, 736),
+            ((1976, 12), PersonnelCategory::Man, 756),
+            ((1980, 1), PersonnelCategory::Man, 756),
+            ((1969, 12), PersonnelCategory::FemaleCadres, 660),
+            ((1971, 4), PersonnelCategory::FemaleCadres, 664),
+            ((1985, 1), PersonnelCategory::FemaleCadres, 696),
+            ((1974, 12), PersonnelCategory::FemaleWorkers, 600),
+            ((1975, 1), PersonnelCategory::FemaleWorkers, 601),
+            ((1980, 1), PersonnelCategory::FemaleWorkers, 631),
+            ((1984, 12), PersonnelCategory::FemaleWorkers, 660),
+        ];
+        for ((year, month), types, working) in expected {
+            let birth = Date::new(year, month);
+            assert_eq!(months(rule_set.calculate_working_date(&birth, &types)), Some(working), "{birth} {types:?}");
+        }
+        for (types, original) in [(PersonnelCategory::Man, 720), (PersonnelCategory::FemaleCadres, 660), (PersonnelCategory::FemaleWorkers, 600)] {
+            for year in [1950, 1970, 1990] {
+                assert_eq!(months(rule_set.baseline().calculate_working_date(&Date::new(year, 6), &types)), Some(original));
+            }
+        }
+        assert_eq!(rule_set.policies.len(), 2);
+        assert_eq!(rule_set.baseline().name, "国发〔1978〕104号");
+
+        // 地区政策: 一次延迟两个月, 原退休年龄带月数
+        let regional = RuleSet::parse(r#"
+            [[policy]]
+            name = "base"
+            effective = "2000-01"
+            [policy.categories."男职工"]
+            base_age = 60
+            base_age_months = 6
+
+            [[policy]]
+            name = "delay"
+            effective = "2030-01"
+            [policy.categories."男职工"]
+            start = "1970-01"
+            step = 6
+            delay_per_step = 2
+            max_delay = 12
+        "#).unwrap();
+        let working = |birth: Date| months(regional.calculate_working_date(&birth, &PersonnelCategory::Man)).unwrap();
+        assert_eq!(working(Date::new(1969, 12)), 60 * 12 + 6);
+        assert_eq!(working(Date::new(1970, 1)), 60 * 12 + 8);
+        assert_eq!(working(Date::new(1970, 7)), 60 * 12 + 10);
+        assert_eq!(working(Date::new(1990, 1)), 60 * 12 + 18);
+        assert_eq!(regional.calculate_working_date(&Date::new(1970, 1), &PersonnelCategory::FemaleWorkers), None);
+
+        let error = |text: &str| RuleSet::parse(text).unwrap_err().to_string();
+        assert!(error("").contains("policy"));
+        assert!(error("[[policy]]\nname = \"x\"\neffective = \"2025-13\"").contains("not a YYYY-MM date"));
+        assert!(error("[[policy]]\nname = \"x\"\neffective = \"2025-01\"\n[policy.categories.\"女职工\"]\nbase_age = 50").contains("unknown category"));
+        assert!(error("[[policy]]\nname = \"x\"\neffective = \"2025-01\"\n[policy.categories.\"男职工\"]\nstart = \"1965-01\"").contains("together"));
+        assert!(error("[[policy]]\nname = \"x\"\neffective = \"2025-01\"\n[policy.categories.\"男职工\"]\nstart = \"1965-01\"\nstep = 0\nmax_delay = 3").contains("positive"));
+        assert!(error("[[policy]]\nname = \"x\"\neffective = \"2025-01\"\n[policy.categories.\"男职工\"]\nbase_age = 60\nretire = 1").contains("unknown field"));
+    }
+
+    #[test]
+    fn test_flexible_retirement() {
+        use super::retirement::date::Date;
+        use super::retirement::flexible::{plan_retirement, ContributionHistory, RetirementKind, RetirementRejection};
+        use super::retirement::flexible_retire_time;
+        use super::retirement::rules::RuleSet;
+        use super::retirement::types::PersonnelCategory;
+
+        let history: ContributionHistory = "2000-01..2010-12, 2005-01..2012-12".parse().unwrap();
+        assert_eq!(history.months_before(&Date::new(2013, 1)), 156);
+        assert_eq!(history.months_before(&Date::new(2011, 1)), 132);
+        assert_eq!(history.months_before(&Date::new(1999, 1)), 0);
+        assert!("2000-01-2010-12".parse::<ContributionHistory>().is_err());
+        assert!("2010-12..2000-01".parse::<ContributionHistory>().is_err());
+        assert_eq!("".parse::<ContributionHistory>().unwrap(), ContributionHistory::default());
+
+        // 最低缴费年限自 2030 年起每年提高六个月, 至 20 年为止
+        let rules = RuleSet::builtin();
+        assert_eq!(rules.minimum_contribution(&Date::new(2029, 12)), 180);
+        assert_eq!(rules.minimum_contribution(&Date::new(2030, 1)), 186);
+        assert_eq!(rules.minimum_contribution(&Date::new(2039, 12)), 240);
+        assert_eq!(rules.minimum_contribution(&Date::new(2050, 1)), 240);
+
+        // 1970-01 出生的男职工: 法定退休 2031-05 (61 岁 4 个月), 原法定退休 2030-01
+        let plan = |requested: Date, history: &str| plan_retirement(
+            &rules, &Date::new(1970, 1), &PersonnelCategory::Man, &requested, &history.parse().unwrap());
+        let early = plan(Date::new(2030, 1), "2000-01..2029-12").unwrap();
+        assert_eq!((early.statutory, early.kind, early.adjustment, early.contributed), (Date::new(2031, 5), RetirementKind::Early, -16, 360));
+        assert_eq!(plan(Date::new(2029, 12), "2000-01..2029-12"),
+                   Err(RetirementRejection::BeforeOriginalAge { requested: Date::new(2029, 12), original: Date::new(2030, 1) }));
+        assert_eq!(plan(Date::new(2030, 1), "2015-01..2029-12"),
+                   Err(RetirementRejection::InsufficientContribution { requested: Date::new(2030, 1), required: 186, contributed: 180 }));
+        let statutory = plan(Date::new(2031, 5), "").unwrap();
+        assert_eq!((statutory.kind, statutory.minimum_contribution, statutory.contributed), (RetirementKind::Statutory, 192, 0));
+        assert_eq!(plan(Date::new(2034, 5), "").unwrap().kind, RetirementKind::Late);
+        assert!(matches!(plan(Date::new(2034, 6), ""), Err(RetirementRejection::TooLate { max_late: 36, .. })));
+
+        // 1985-01 出生的原 50 周岁女职工: 法定退休 2040-01, 最早可在 2037-01 退休
+        let rejection = plan_retirement(&rules, &Date::new(1985, 1), &PersonnelCategory::FemaleWorkers,
+                                        &Date::new(2036, 12), &"2000-01..2036-11".parse().unwrap()).unwrap_err();
+        assert_eq!(rejection, RetirementRejection::TooEarly { requested: Date::new(2036, 12), earliest: Date::new(2037, 1), max_early: 36 });
+        assert_eq!(rejection.to_string(), "2036-12 早于最早可以退休的 2037-01, 提前退休最长不超过3年");
+
+        // 弹性退休实施之前只能按法定退休年龄退休
+        assert!(matches!(plan_retirement(&rules, &Date::new(1963, 12), &PersonnelCategory::Man, &Date::new(2024, 6), &history),
+                         Err(RetirementRejection::NotAvailable { .. })));
+
+        assert_eq!(flexible_retire_time("1970-01", "男职工", "2030-01", "2000-01..2029-12"), Ok("2030-01,60,-16".to_string()));
+        assert_eq!(flexible_retire_time("1971-04", "原法定退休年龄55周岁女职工", "2026-08", ""), Ok("2026-08,55.33,0".to_string()));
+        assert_eq!(flexible_retire_time("1970-01", "男职工", "2030-01", "2015-01..2029-12"),
+                   Err("2030-01 退休的最低缴费年限为15年6个月, 已缴费15年, 未达到最低缴费年限不能提前退休".to_string()));
+        assert!(flexible_retire_time("1970-13", "男职工", "2030-01", "").is_err());
+    }
 }
@@ -78,7 +78,12 @@ fn fac(x: u128, max_factor: &mut u128) {
         return;
     }
     // Miller Rabin 算法 判断是否为素数，如果是, 直接返回
-    if miller_rabin(x) {
+    // u64 范围内使用 number_theory 中确定性的实现
+    let prime = match u64::try_from(x) {
+        Ok(x) => number_theory::is_prime(x),
+        Err(_) => miller_rabin(x),
+    };
+    if prime {
         *max_factor = std::cmp::max(*max_factor, x);
         return;
     }
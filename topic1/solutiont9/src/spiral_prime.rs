@@ -1,5 +1,4 @@
 use std::fmt::format;
-use const_primes::Primes;
 
 /// 求对角线上素数的比例第一次低于某个比例时，螺旋数阵的边长是多少，此时有多少个素数？（比例不会小于 7%)
 /// 对角线上素数的比例第一次低于 threshold / 100 时返回当前边长与素数
@@ -38,8 +37,8 @@ fn how_many_prime_edges_in_this_turn(this_turn: u32) -> u32 {
     for _ in 0..3 { // 没有必要计算右下角
         cnt += 2 * this_turn as u64;
         // 此处自己实现的 Miller-Rabin 素性测试算法性能不够, 无法在规定时间内完成计算
-        // 因此采用 const-primes 提供的高性能 Miller-Rabin 素性测试算法
-        if const_primes::is_prime(cnt as u64) {
+        // 因此采用工作区内 number_theory 提供的确定性 Miller-Rabin 素性测试算法
+        if number_theory::is_prime(cnt) {
             prime_cnt += 1;
         }
     }